@@ -0,0 +1,26 @@
+use crate::Global;
+
+static CLOCK: Global<Option<&'static dyn Clock>> = Global::new(None);
+
+/// A source of monotonic time, used to throttle logging.
+///
+/// Install one with [`set_clock`]. This crate is `no_std`, so embedded users can wire in their
+/// own timer, while `std` users might measure from an `Instant` taken at startup.
+pub trait Clock: Sync {
+    /// Returns the number of milliseconds elapsed since some fixed point in time.
+    fn now_millis(&self) -> u64;
+}
+
+/// Installs the clock used by throttled methods such as
+/// [`unwrap_or_default_log_throttled`](crate::OptionExt::unwrap_or_default_log_throttled).
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+/// Until a clock is installed, throttled methods log every failure.
+pub fn set_clock(clock: &'static dyn Clock) {
+    CLOCK.set(Some(clock));
+}
+
+#[cfg(target_has_atomic = "64")]
+pub(crate) fn clock() -> Option<&'static dyn Clock> {
+    CLOCK.get()
+}
@@ -0,0 +1,37 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::panic::Location;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::Global;
+
+static DEDUP: AtomicBool = AtomicBool::new(false);
+static COUNTS: Global<BTreeMap<&'static Location<'static>, u64>> = Global::new(BTreeMap::new());
+
+/// Enables or disables deduplication of repeated failures at the same call site.
+///
+/// While enabled, each call site's failures are tallied, and only the 1st, 2nd, 4th, 8th, ...
+/// are logged, suffixed with the tally, as in ``encountered `None` (x8)``.
+/// This is process-global, and affects all call sites, including those in other crates.
+pub fn set_dedup(enabled: bool) {
+    DEDUP.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns each call site which failed while deduplication was enabled, with its tally.
+///
+/// See [`set_dedup`]. Call sites are ordered by file, line, then column.
+pub fn failure_report() -> Vec<(&'static Location<'static>, u64)> {
+    COUNTS.with(|counts| counts.iter().map(|(&k, &v)| (k, v)).collect())
+}
+
+/// Tallies a failure at `caller`, returning the new tally if deduplication is enabled.
+pub(crate) fn occurrence(caller: &'static Location<'static>) -> Option<u64> {
+    if !DEDUP.load(Ordering::Relaxed) {
+        return None;
+    }
+    Some(COUNTS.with(|counts| {
+        let count = counts.entry(caller).or_insert(0);
+        *count += 1;
+        *count
+    }))
+}
@@ -0,0 +1,173 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::panic::Location;
+
+use crate::PayloadFormat;
+
+/// Extension trait providing a tracing alternative to `Iterator::filter_map(Result::ok)`.
+///
+/// ```rust
+/// use unwrap_log::IterResultExt;
+///
+/// let ports: Vec<u16> = ["80", "http", "443"]
+///     .into_iter()
+///     .map(str::parse)
+///     .filter_map_ok_log()
+///     .collect();
+/// assert_eq!(ports, [80, 443]);
+/// ```
+pub trait IterResultExt: Iterator + Sized {
+    /// The type of the "successful" items, intended to be `T` for an iterator of `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" items, intended to be `E` for an iterator of `Result<T, E>`.
+    type Error;
+    /// Yields the contained `Ok` values, logging each `Err` at the [default level](crate::default_level).
+    ///
+    /// Failures are reported at the location where the adapter was created, rather than where
+    /// it was consumed.
+    fn filter_map_ok_log(self) -> FilterMapOkLog<Self>;
+    /// Collects the contained `Ok` and `Err` values apart, logging each `Err` at the
+    /// [default level](crate::default_level) as it goes.
+    ///
+    /// ```rust
+    /// use unwrap_log::IterResultExt;
+    ///
+    /// let (ports, errors) = ["80", "http", "443", "ftp"]
+    ///     .into_iter()
+    ///     .map(str::parse::<u16>)
+    ///     .partition_log();
+    /// assert_eq!(ports, [80, 443]);
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn partition_log(self) -> (Vec<Self::Output>, Vec<Self::Error>);
+}
+
+/// Extension trait providing a tracing alternative to `Iterator::flatten` for iterators of `Option`s.
+///
+/// ```rust
+/// use unwrap_log::IterOptionExt;
+///
+/// let names: Vec<&str> = [Some("ferris"), None, Some("corro")]
+///     .into_iter()
+///     .flatten_log()
+///     .collect();
+/// assert_eq!(names, ["ferris", "corro"]);
+/// ```
+pub trait IterOptionExt: Iterator + Sized {
+    /// The type of the "present" items, intended to be `T` for an iterator of `Option<T>`.
+    type Output;
+    /// Yields the contained `Some` values, logging each `None` at the [default level](crate::default_level).
+    ///
+    /// Failures are reported at the location where the adapter was created, rather than where
+    /// it was consumed.
+    fn flatten_log(self) -> FlattenLog<Self>;
+}
+
+/// The iterator returned by [`IterResultExt::filter_map_ok_log`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FilterMapOkLog<I> {
+    iter: I,
+    location: &'static Location<'static>,
+}
+
+/// The iterator returned by [`IterOptionExt::flatten_log`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FlattenLog<I> {
+    iter: I,
+    location: &'static Location<'static>,
+}
+
+impl<I, T, E> IterResultExt for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: PayloadFormat,
+{
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn filter_map_ok_log(self) -> FilterMapOkLog<Self> {
+        FilterMapOkLog {
+            iter: self,
+            location: Location::caller(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn partition_log(self) -> (Vec<T>, Vec<E>) {
+        let location = Location::caller();
+        let (mut oks, mut errs) = (Vec::new(), Vec::new());
+        for item in self {
+            match item {
+                Ok(x) => oks.push(x),
+                Err(err) => {
+                    crate::result_error_at(location, None, &err);
+                    errs.push(err);
+                }
+            }
+        }
+        (oks, errs)
+    }
+}
+
+impl<I, T> IterOptionExt for I
+where
+    I: Iterator<Item = Option<T>>,
+{
+    type Output = T;
+
+    #[track_caller]
+    fn flatten_log(self) -> FlattenLog<Self> {
+        FlattenLog {
+            iter: self,
+            location: Location::caller(),
+        }
+    }
+}
+
+impl<I, T, E> Iterator for FilterMapOkLog<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: PayloadFormat,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for item in &mut self.iter {
+            match item {
+                Ok(x) => return Some(x),
+                Err(err) => crate::result_error_at(self.location, None, &err),
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, T> Iterator for FlattenLog<I>
+where
+    I: Iterator<Item = Option<T>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for item in &mut self.iter {
+            match item {
+                Some(x) => return Some(x),
+                None => crate::option_error_at(self.location, None),
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -0,0 +1,155 @@
+//! Levels at which failures are reported, as values, and as types for the `_as` methods.
+
+/// The level at which a failure is reported.
+///
+/// This mirrors `log::Level`, so it is available without the `log` feature, and converts to and
+/// from it with that feature. As there, `Error` is the most severe level, and compares least.
+///
+/// ```rust
+/// use unwrap_log::Level;
+///
+/// assert!(Level::Error < Level::Warn);
+/// assert_eq!(Level::Trace.to_string(), "TRACE");
+/// #[cfg(feature = "log")]
+/// assert_eq!(Level::from(log::Level::Info), Level::Info);
+/// #[cfg(feature = "log")]
+/// assert_eq!(log::Level::from(Level::Debug), log::Level::Debug);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Failures which shouldn't have happened.
+    Error = 1,
+    /// Failures which are suspicious, but survivable.
+    Warn,
+    /// Failures worth noting, but expected now and then.
+    Info,
+    /// Failures of interest only while debugging.
+    Debug,
+    /// Failures of interest only while tracing execution in detail.
+    Trace,
+}
+
+impl Level {
+    /// Returns the name of this level, in upper case, as `log` renders it.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+
+    /// Returns the level named `name`, ignoring case.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Error,
+            Self::Warn,
+            Self::Info,
+            Self::Debug,
+            Self::Trace,
+        ]
+        .into_iter()
+        .find(|level| level.as_str().eq_ignore_ascii_case(name))
+    }
+
+    pub(crate) const fn from_u8(level: u8) -> Self {
+        match level {
+            1 => Self::Error,
+            2 => Self::Warn,
+            3 => Self::Info,
+            4 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl Level {
+    /// Returns whether the static and dynamic maximum levels of `log` let this level through.
+    pub(crate) fn within_log_max(self) -> bool {
+        let level = log::Level::from(self);
+        level <= log::STATIC_MAX_LEVEL && level <= log::max_level()
+    }
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Self::Error,
+            log::Level::Warn => Self::Warn,
+            log::Level::Info => Self::Info,
+            log::Level::Debug => Self::Debug,
+            log::Level::Trace => Self::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<Level> for log::Level {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => Self::Error,
+            Level::Warn => Self::Warn,
+            Level::Info => Self::Info,
+            Level::Debug => Self::Debug,
+            Level::Trace => Self::Trace,
+        }
+    }
+}
+
+/// A level fixed at compile time, for methods such as
+/// [`unwrap_or_default_log_as`](crate::OptionExt::unwrap_or_default_log_as).
+///
+/// Where the `max_level_*` or `release_max_level_*` features of `log` compile the level out,
+/// those methods only count a failure, as [`failure_count`](crate::failure_count) sees it, and
+/// leave out the code which would format and report it. This applies to every backend and sink,
+/// not just `log`. Without the `log` feature, no level is compiled out.
+///
+/// ```rust
+/// use unwrap_log::level::{StaticLevel, Warn};
+/// use unwrap_log::Level;
+///
+/// assert_eq!(Warn::LEVEL, Level::Warn);
+/// ```
+pub trait StaticLevel {
+    /// The level this type stands for.
+    const LEVEL: Level;
+}
+
+macro_rules! static_levels {
+    ($($name:ident),*) => {$(
+        #[doc = concat!("[`Level::", stringify!($name), "`], as a type.")]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl StaticLevel for $name {
+            const LEVEL: Level = Level::$name;
+        }
+    )*};
+}
+
+static_levels!(Error, Warn, Info, Debug, Trace);
+
+/// Returns whether the static maximum level of `log` compiles `level` out.
+pub(crate) const fn compiled_out(level: Level) -> bool {
+    #[cfg(feature = "log")]
+    {
+        // Both enums number their levels from `Error` as 1.
+        level as usize > log::STATIC_MAX_LEVEL as usize
+    }
+    #[cfg(not(feature = "log"))]
+    {
+        let _ = level;
+        false
+    }
+}
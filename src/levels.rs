@@ -0,0 +1,116 @@
+use crate::{Global, Level};
+use alloc::collections::BTreeMap;
+
+static LEVELS: Global<BTreeMap<&'static str, Level>> = Global::new(BTreeMap::new());
+
+/// Overrides the [default level](crate::default_level) for failures in `module` and its
+/// submodules.
+///
+/// This applies to every method and macro which would log at the default level, but not to
+/// those given a level, such as `unwrap_or_default_log_at`. Only the macros know the module of
+/// their call site, as described for [`set_target`](crate::set_target), so the methods always
+/// log at the default level.
+///
+/// `module` is a path as `module_path!()` renders it, such as `"myapp::parser"`, and matches
+/// both that module and those nested within it. Where several overrides match, the longest
+/// wins, and where none does, the default level applies. Setting the same `module` again
+/// replaces its level. This is process-global, and only available with the `alloc` feature.
+///
+/// ```rust
+/// use unwrap_log::Level;
+///
+/// unwrap_log::set_module_level("myapp::parser", Level::Error);
+/// unwrap_log::set_module_level("myapp::parser::lenient", Level::Debug);
+///
+/// assert_eq!(unwrap_log::module_level("myapp::parser::expr"), Some(Level::Error));
+/// assert_eq!(unwrap_log::module_level("myapp::parser::lenient"), Some(Level::Debug));
+/// assert_eq!(unwrap_log::module_level("myapp::parsers"), None);
+/// assert_eq!(unwrap_log::module_level("myapp::cache"), None);
+/// ```
+///
+/// The macros honour an override, while the methods don't:
+///
+/// ```rust
+/// use core::fmt::Arguments;
+/// use core::panic::Location;
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use unwrap_log::{unwrap_or_default_log, Level, OptionExt, Sink};
+///
+/// static LEVEL: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Levels;
+///
+/// impl Sink for Levels {
+///     fn report(&self, level: Level, _: &Location<'_>, _: Arguments<'_>) {
+///         LEVEL.store(level as usize, Ordering::Relaxed);
+///     }
+/// }
+///
+/// unwrap_log::set_sink(&Levels);
+/// unwrap_log::set_module_level(module_path!(), Level::Debug);
+/// let x: i32 = unwrap_or_default_log!(None);
+/// assert_eq!(LEVEL.load(Ordering::Relaxed), Level::Debug as usize);
+/// let x: i32 = None.unwrap_or_default_log();
+/// assert_eq!(LEVEL.load(Ordering::Relaxed), unwrap_log::default_level() as usize);
+/// ```
+pub fn set_module_level(module: &'static str, level: impl Into<Level>) {
+    let level = level.into();
+    LEVELS.with(|levels| levels.insert(module, level));
+}
+
+/// Removes every override set with [`set_module_level`].
+pub fn clear_module_levels() {
+    LEVELS.with(BTreeMap::clear);
+}
+
+/// Returns the level overriding the default for failures in `module`, if any.
+///
+/// See [`set_module_level`].
+pub fn module_level(module: &str) -> Option<Level> {
+    LEVELS.with(|levels| {
+        levels
+            .iter()
+            .filter(|(prefix, _)| {
+                module
+                    .strip_prefix(*prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, &level)| level)
+    })
+}
+
+/// Returns the level overriding the default for a failure in `module`, if it is known.
+pub(crate) fn level_at(module: Option<&str>) -> Option<Level> {
+    if LEVELS.with(|levels| levels.is_empty()) {
+        return None;
+    }
+    module_level(module?)
+}
+
+/// Like `unwrap_or_default_log`, but logs at the level [overriding](set_module_level) the
+/// default for the calling module, if any.
+///
+/// Every macro honours these overrides, so this differs from
+/// [`unwrap_or_default_log!`](crate::unwrap_or_default_log) only in leaving the expression out
+/// of the message. This is only available with the `alloc` feature.
+///
+/// ```rust
+/// use unwrap_log::{unwrap_or_default_log_module, Level};
+///
+/// unwrap_log::set_module_level(module_path!(), Level::Debug);
+/// let x: i32 = unwrap_or_default_log_module!(None);
+/// assert_eq!(x, 0);
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log_module {
+    ($e:expr $(,)?) => {{
+        let value = $e;
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!());
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
@@ -1,4 +1,4 @@
-//! Non-panicking alternatives to `Option` and `Result` unwrapping, which log at warn level.
+//! Non-panicking alternatives to `Option` and `Result` unwrapping, which log at warn level by default.
 //!
 //! ## Example
 //!
@@ -21,172 +21,5533 @@
 //! [1970-01-01T00:00:00Z WARN  my_crate] src\main.rs:8:23 encountered `None`
 //! [1970-01-01T00:00:00Z WARN  my_crate] src\main.rs:11:30 encountered `Err("oops")`
 //! ```
+//!
+//! ## Choosing a trait
+//!
+//! [`ResultExt`] formats the error with `Debug`. For errors which aren't `Debug`, or shouldn't be
+//! logged in full, the alternative traits format it differently, and suffix their method names to
+//! match:
+//!
+//! - [`ResultExtNoDbg`] (`_no_dbg`) doesn't format the error at all.
+//! - [`ResultExtDisplay`] (`_display`) formats it with `Display`.
+//! - [`ResultExtRedact`] (`_redacted`) logs a token chosen by a closure.
+//! - [`ResultExtCode`] (`_code`) logs a numeric code chosen by a closure.
+//! - `ResultExtSource` (`_source`, with `std`) formats its chain of `source`s.
+//! - `ResultExtAnyhow` (`_anyhow`, with `anyhow`) formats an `anyhow::Error` with its contexts.
+//!
+//! Since no two traits share a method name, any of them may be imported together, and method
+//! resolution never has to choose between them. An inherent method of `Option` or `Result` would
+//! take precedence over any of these, but none share a name with them.
+//!
+//! ```rust
+//! use unwrap_log::{ResultExt, ResultExtNoDbg};
+//!
+//! struct Opaque;
+//!
+//! let x: i32 = Err("oops").unwrap_or_default_log();
+//! let y: i32 = Err(Opaque).unwrap_or_default_log_no_dbg();
+//! assert_eq!(x + y, 0);
+//! ```
+//!
+//! The [`prelude`] re-exports every extension trait, for glob-importing them all at once.
+//!
+//! ## Writing wrappers
+//!
+//! Every method reports the location of its caller. A function which wraps one of them reports
+//! its own body instead, unless it is `#[track_caller]` too, in which case the location is
+//! forwarded to the wrapper's caller:
+//!
+//! ```rust
+//! use core::panic::Location;
+//! use core::sync::atomic::{AtomicU32, Ordering};
+//! use unwrap_log::{FailureKind, OptionExt};
+//!
+//! static LINE: AtomicU32 = AtomicU32::new(0);
+//!
+//! fn record(location: &Location<'_>, _: FailureKind) {
+//!     LINE.store(location.line(), Ordering::Relaxed);
+//! }
+//!
+//! fn lookup(_name: &str) -> Option<u16> {
+//!     None
+//! }
+//!
+//! #[track_caller]
+//! fn setting(name: &str) -> u16 {
+//!     lookup(name).unwrap_or_default_log()
+//! }
+//!
+//! unwrap_log::set_hook(record);
+//! let line = line!() + 1;
+//! let port = setting("port");
+//! assert_eq!(LINE.load(Ordering::Relaxed), line);
+//! ```
+//!
+//! `#[track_caller]` doesn't reach through closures, so a wrapper which calls into this crate
+//! from a closure should capture [`Location::caller`](core::panic::Location::caller) outside of
+//! it, and pass it to one of the `_at_location` methods, such as
+//! [`unwrap_or_default_log_at_location`](OptionExt::unwrap_or_default_log_at_location).
+//!
+//! ## Fallback closures
+//!
+//! Every method which computes a fallback with a closure, such as
+//! [`unwrap_or_else_log`](OptionExt::unwrap_or_else_log), reports the failure before calling
+//! it, so the failure is still logged if the closure itself panics:
+//!
+//! ```rust
+//! use core::panic::Location;
+//! use core::sync::atomic::{AtomicU32, Ordering};
+//! use std::panic::catch_unwind;
+//! use unwrap_log::prelude::*;
+//! use unwrap_log::{level, FailureKind, Level};
+//!
+//! static REPORTED: AtomicU32 = AtomicU32::new(0);
+//!
+//! fn record(_: &Location<'_>, _: FailureKind) {
+//!     REPORTED.fetch_add(1, Ordering::Relaxed);
+//! }
+//!
+//! macro_rules! reported_before_panic {
+//!     ($e:expr) => {
+//!         let before = REPORTED.load(Ordering::Relaxed);
+//!         assert!(catch_unwind(|| $e).is_err());
+//!         assert_eq!(REPORTED.load(Ordering::Relaxed), before + 1);
+//!     };
+//! }
+//!
+//! unwrap_log::set_hook(record);
+//! std::panic::set_hook(Box::new(|_| {}));
+//! let none = || None::<i32>;
+//! let err = || Err::<i32, &str>("oops");
+//! let here = Location::caller();
+//! reported_before_panic!(none().unwrap_or_else_log(|| panic!()));
+//! reported_before_panic!(none().unwrap_or_else_log_at(Level::Error, || panic!()));
+//! reported_before_panic!(none().unwrap_or_else_log_as::<level::Error>(|| panic!()));
+//! reported_before_panic!(none().unwrap_or_else_log_at_location(here, || panic!()));
+//! reported_before_panic!(none().unwrap_or_else_log_verbose(|| panic!()));
+//! reported_before_panic!(none().unwrap_or_else_expect_log("msg", || panic!()));
+//! reported_before_panic!(none().ok_or_else_log::<()>(|| panic!()));
+//! reported_before_panic!(none().or_else_log(|| panic!()));
+//! reported_before_panic!(none().map_or_else_log(|| panic!(), |x| x));
+//! reported_before_panic!(none().inspect_none_with(|| panic!()));
+//! reported_before_panic!(*none().get_or_insert_with_log(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_with(|_| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_ref(|_| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_at(Level::Error, || panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_as::<level::Error>(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_at_location(here, || panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_verbose(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_expect_log("msg", || panic!()));
+//! reported_before_panic!(err().or_else_log::<()>(|_| panic!()));
+//! reported_before_panic!(err().map_or_else_log(|_| panic!(), |x| x));
+//! reported_before_panic!(err().inspect_err_with(|_| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_no_dbg(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_with_no_dbg(|_| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_ref_no_dbg(|_| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_at_no_dbg(Level::Error, || panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_as_no_dbg::<level::Error>(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_at_location_no_dbg(here, || panic!()));
+//! reported_before_panic!(err().unwrap_or_else_expect_log_no_dbg("msg", || panic!()));
+//! reported_before_panic!(err().map_or_else_log_no_dbg(|_| panic!(), |x| x));
+//! reported_before_panic!(err().unwrap_or_else_log_display(|| panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_redacted(|_| "token", || panic!()));
+//! reported_before_panic!(err().unwrap_or_else_log_code(|_| 7, || panic!()));
+//! #[cfg(feature = "std")]
+//! reported_before_panic!("x".parse::<i32>().unwrap_or_else_log_source(|| panic!()));
+//! #[cfg(feature = "anyhow")]
+//! reported_before_panic!(Err::<i32, _>(anyhow::anyhow!("oops")).unwrap_or_else_log_anyhow(|| panic!()));
+//! reported_before_panic!(Ok::<&str, i32>("ok").unwrap_err_or_else_log(|| panic!()));
+//! ```
+//!
+//! ## Interrupts
+//!
+//! Settings such as the [target](set_target), and state such as the location of the last
+//! failure, which don't fit in a single atomic, are kept behind spin locks, which both reporting
+//! and the setters take briefly. The crate is therefore not interrupt-safe: on a single core, an interrupt
+//! handler which reports a failure, or changes a setting, while the code it preempted holds one of
+//! those locks spins forever. On such targets, report from interrupt handlers only through a
+//! channel of your own, or with interrupts masked around every call into this crate.
+//!
+//! ## Features
+//!
+//! - `log` (default): emit through the [`log`](https://docs.rs/log) crate. Records above the
+//!   level chosen by its `max_level_*` and `release_max_level_*` features are dropped before they
+//!   are formatted, though the failures are still counted and passed to the [hook](set_hook).
+//! - `kv`: attach the caller as a structured `location` field, and as separate `file`, `line`,
+//!   and `column` fields, and the error (if any) as an `err` field rather than embedding it in the
+//!   message. Also enables `unwrap_or_default_log_kv`, which attaches a field of the caller's
+//!   choosing. Implies `log`.
+//! - `tracing`: emit through the [`tracing`](https://docs.rs/tracing) crate, with the caller
+//!   recorded in a `location` field. Failures also carry `defaulted = true` and, where there is
+//!   one, the error in an `error` field, while traced successes carry `defaulted = false`. Events
+//!   attach to whichever spans are active at the call site.
+//! - `defmt`: emit through [`defmt`](https://docs.rs/defmt), for embedded targets. This changes
+//!   the payload bound of `ResultExt` and `ResultErrExt` from `Debug` to `defmt::Format`, so other
+//!   backends enabled alongside it render the payload as `Err(..)` or `Ok(..)`.
+//! - `metrics`: increment an `unwrap_log.failures` counter of the
+//!   [`metrics`](https://docs.rs/metrics) crate on every failure counted by [`failure_count`],
+//!   labeled only by `kind`, one of `none`, `err`, `err_no_dbg`, `ok`, `filtered_out` or `some`.
+//!   Implies `std`.
+//! - `alloc`: enables [`set_dedup`], which logs repeated failures at each call site only on
+//!   powers of two, [`failure_report`], which summarizes them, [`last_error`], and
+//!   [`set_module_level`], which overrides the default level per module, and
+//!   [`partition_log`](IterResultExt::partition_log).
+//! - `std`: enables `ResultExtSource`, which logs the chain of `std::error::Error::source`s.
+//!   Implies `alloc`.
+//! - `level-error`: makes the initial [default level](default_level) `Error` rather than `Warn`.
+//!   [`set_default_level`] still overrides it at runtime.
+//! - `fallback`: enables [`set_fallback`], which receives failures that `log` would drop
+//!   because no logger is initialized yet. Implies `log`.
+//! - `seq`: numbers every reported failure from a global sequence, written after the location,
+//!   as in ``#42 encountered `None` ``, for lining up failures interleaved across threads. This
+//!   has no effect on `defmt`, or on targets without 64-bit atomics.
+//! - `async`: enables `OptionExtAsync` and `ResultExtAsync`, whose fallbacks are futures.
+//! - `anyhow`: enables `ResultExtAnyhow`, which logs an `anyhow::Error` with its chain of
+//!   contexts, as `{:#}` renders it. Implies `alloc`.
+//! - `testing`: enables the [`testing`](crate::testing) module, whose sink captures failures for
+//!   tests to assert on. Implies `alloc`.
+//!
+//! These features may be enabled together, in which case every failure is emitted through each.
+//!
+//! With `default-features = false` and none of the backends above, nothing is ever logged, and
+//! every method reduces to its non-logging counterpart plus a bump of [`failure_count`]. Nor is
+//! the `log` crate depended upon: levels are given as this crate's own [`Level`], which converts
+//! to and from `log::Level` with the `log` feature, so the methods taking a level accept either.
 #![no_std]
 
-/// Extension trait providing tracing alternatives to `Option` unwrap methods.
-pub trait OptionExt {
-    /// The type of the "present" output, intended to be `T` for a `Option<T>`.
-    type Output;
-    /// Returns the contained `Some` value, or logs at the warn level and returns a default value.
-    fn unwrap_or_default_log(self) -> Self::Output;
-    /// Returns the contained `Some` value, or logs at the warn level and computes a default value from a closure.
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
-    /// Returns the contained `Some` value, or logs at the warn level and returns the provided default.
-    fn unwrap_or_log(self, default: Self::Output) -> Self::Output;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod clock;
+#[cfg(feature = "alloc")]
+mod dedup;
+mod iter;
+pub mod level;
+#[cfg(feature = "alloc")]
+mod levels;
+mod logged;
+mod mute;
+pub mod prelude;
+#[cfg(target_has_atomic = "64")]
+mod scope;
+mod sink;
+mod site;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use clock::{set_clock, Clock};
+#[cfg(feature = "alloc")]
+pub use dedup::{failure_report, set_dedup};
+pub use iter::{FilterMapOkLog, FlattenLog, IterOptionExt, IterResultExt};
+pub use level::Level;
+#[cfg(feature = "alloc")]
+pub use levels::{clear_module_levels, module_level, set_module_level};
+pub use logged::{Logged, LoggedDefault};
+pub use mute::{mute, MuteGuard};
+#[cfg(target_has_atomic = "64")]
+pub use scope::FailureScope;
+#[cfg(feature = "log")]
+pub use sink::LogSink;
+pub use sink::{add_sink, clear_sink, set_sink, RingSink, Sink, MAX_SINKS};
+
+use level::StaticLevel;
+#[cfg(feature = "log")]
+use sink::Target;
+use site::{Gate, Site};
+
+/// Items for the macros of this crate, which aren't part of its API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::site::{Gate, Site};
+}
+
+use core::cell::UnsafeCell;
+#[cfg(not(feature = "defmt"))]
+use core::fmt::Debug as PayloadFormat;
+use core::ops::ControlFlow;
+use core::panic::Location;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU8, Ordering};
+#[cfg(feature = "defmt")]
+use defmt::Format as PayloadFormat;
+
+/// A type-erased payload bound by `PayloadFormat`.
+#[cfg(not(feature = "defmt"))]
+type DynPayload<'a> = dyn core::fmt::Debug + 'a;
+#[cfg(feature = "defmt")]
+type DynPayload<'a> = dyn ErasedFormat + 'a;
+
+#[cfg(not(feature = "level-error"))]
+static DEFAULT_LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+#[cfg(feature = "level-error")]
+static DEFAULT_LEVEL: AtomicU8 = AtomicU8::new(Level::Error as u8);
+static DEFAULT_OFF: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "log")]
+static TRACE_DETAILS: AtomicBool = AtomicBool::new(false);
+static PRETTY_DEBUG: AtomicBool = AtomicBool::new(false);
+static TARGET: Global<Option<&'static str>> = Global::new(None);
+static PREFIX: Global<Option<&'static str>> = Global::new(None);
+static PATH_PREFIX: Global<Option<&'static str>> = Global::new(None);
+static MESSAGES: Global<Messages> = Global::new(Messages::ENGLISH);
+#[cfg(target_has_atomic = "64")]
+static FAILURE_COUNT: AtomicU64 = AtomicU64::new(0);
+#[cfg(target_has_atomic = "64")]
+static PANIC_THRESHOLD: AtomicU64 = AtomicU64::new(u64::MAX);
+static HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+#[cfg(all(feature = "seq", target_has_atomic = "64"))]
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "fallback")]
+static FALLBACK: Global<Option<fn(&str)>> = Global::new(None);
+#[cfg(target_has_atomic = "64")]
+static LAST_FAILURE: Global<Option<&'static Location<'static>>> = Global::new(None);
+#[cfg(feature = "alloc")]
+static LAST_ERROR: Global<Option<alloc::string::String>> = Global::new(None);
+
+/// Sets the level used by methods which don't take an explicit level.
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+/// The initial default level is `Warn`, or `Error` with the `level-error` feature, and is
+/// overridden by this. This also undoes an `off` read by [`init_from_env`].
+pub fn set_default_level(level: impl Into<Level>) {
+    DEFAULT_LEVEL.store(level.into() as u8, Ordering::Relaxed);
+    DEFAULT_OFF.store(false, Ordering::Relaxed);
+}
+
+/// Sets the [default level](default_level) from the `UNWRAP_LOG_LEVEL` environment variable.
+///
+/// The variable may be any of `off`, `error`, `warn`, `info`, `debug`, or `trace`, ignoring case.
+/// `off` stops methods which don't take an explicit level from logging, though their failures
+/// are still counted and passed to the [hook](set_hook). If the variable is unset or can't be
+/// parsed, the default level is left unchanged. This is only available with the `std` feature.
+///
+/// ```rust
+/// std::env::set_var("UNWRAP_LOG_LEVEL", "debug");
+/// unwrap_log::init_from_env();
+/// assert_eq!(unwrap_log::default_level(), unwrap_log::Level::Debug);
+/// ```
+#[cfg(feature = "std")]
+pub fn init_from_env() {
+    let Ok(var) = std::env::var("UNWRAP_LOG_LEVEL") else {
+        return;
+    };
+    if var.eq_ignore_ascii_case("off") {
+        DEFAULT_OFF.store(true, Ordering::Relaxed);
+    } else if let Some(level) = Level::from_name(&var) {
+        set_default_level(level);
+    }
+}
+
+/// Returns the level used by methods which don't take an explicit level.
+///
+/// See [`set_default_level`].
+pub fn default_level() -> Level {
+    Level::from_u8(DEFAULT_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Enables or disables a second, detailed `trace` record after each failure logged through `log`.
+///
+/// While enabled, each failure logged at a level above `trace` is logged twice: once tersely,
+/// as usual, then again at `trace` with its [kind](FailureKind), and with the `std` feature,
+/// the current thread and a backtrace. The second record is only built if the logger has
+/// `trace` enabled for the target, so the terse path stays cheap when it doesn't.
+///
+/// This is process-global, and only available with the `log` feature.
+///
+/// ```rust
+/// unwrap_log::set_trace_details(true);
+/// // With `trace` enabled, failures now log as, e.g.,
+/// // `src/main.rs:8:23 encountered `None`` at `warn`, then
+/// // `src/main.rs:8:23 encountered `None` [kind: None, thread: main]` and a backtrace at `trace`.
+/// ```
+#[cfg(feature = "log")]
+pub fn set_trace_details(enabled: bool) {
+    TRACE_DETAILS.store(enabled, Ordering::Relaxed);
+}
+
+/// Sets whether errors are formatted in messages with the alternate `{:#?}`, rather than the
+/// compact `{:?}`.
+///
+/// The alternate form is usually spread over several lines, which suits large error types.
+/// This only changes the rendered message, not the `err` field with `kv` or `tracing`, nor
+/// [`last_error`](crate::last_error).
+///
+/// This is process-global. With `defmt`, errors are not formatted with `Debug`, so this only
+/// affects `ResultExtSource`.
+///
+/// ```rust
+/// use unwrap_log::ResultExt;
+///
+/// #[derive(Debug)]
+/// struct Error {
+///     code: u32,
+/// }
+///
+/// unwrap_log::set_pretty_debug(true);
+/// // Logs "... encountered `Err(Error {\n    code: 7,\n})`".
+/// let x: u8 = Err(Error { code: 7 }).unwrap_or_default_log();
+/// unwrap_log::set_pretty_debug(false);
+/// ```
+pub fn set_pretty_debug(enabled: bool) {
+    PRETTY_DEBUG.store(enabled, Ordering::Relaxed);
+}
+
+/// Sets the target of emitted `log` records, so they can be filtered separately.
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+/// Until this is called, records are emitted with the caller's module as their target, if
+/// known, or else with the default target of this crate.
+///
+/// Each record carries the file and line of the failing call site. The macros of this crate
+/// also capture the `module_path!()` of their call site, which the record carries as its module
+/// path, and as its target unless one is set. The methods can't, since `#[track_caller]`
+/// doesn't reveal it, so their records have no module path, and the default target. To filter
+/// failures by module, as with `RUST_LOG=my_app=warn`, use the macros.
+///
+#[cfg_attr(feature = "log", doc = "```rust")]
+#[cfg_attr(not(feature = "log"), doc = "```rust,ignore")]
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use unwrap_log::{unwrap_or_default_log, OptionExt};
+///
+/// static LINE: AtomicU32 = AtomicU32::new(0);
+/// static METHOD: AtomicU32 = AtomicU32::new(0);
+///
+/// struct Logger;
+///
+/// impl log::Log for Logger {
+///     fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+///         true
+///     }
+///     fn log(&self, record: &log::Record<'_>) {
+///         assert_eq!(record.file(), Some(file!()));
+///         if record.line() == Some(METHOD.load(Ordering::Relaxed)) {
+///             assert_eq!(record.module_path(), None);
+///             assert_eq!(record.target(), "unwrap_log");
+///         } else {
+///             assert_eq!(record.module_path(), Some(module_path!()));
+///             assert_eq!(record.target(), module_path!());
+///         }
+///         LINE.store(record.line().unwrap(), Ordering::Relaxed);
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// log::set_logger(&Logger).unwrap();
+/// log::set_max_level(log::LevelFilter::Warn);
+/// let line = line!() + 1;
+/// let x: i32 = unwrap_or_default_log!(None);
+/// assert_eq!(LINE.load(Ordering::Relaxed), line);
+///
+/// METHOD.store(line!() + 1, Ordering::Relaxed);
+/// let x: i32 = None.unwrap_or_default_log();
+/// assert_eq!(LINE.load(Ordering::Relaxed), METHOD.load(Ordering::Relaxed));
+/// ```
+pub fn set_target(target: &'static str) {
+    TARGET.set(Some(target));
+}
+
+/// Returns the target set by [`set_target`], if any.
+pub fn target() -> Option<&'static str> {
+    TARGET.get()
+}
+
+/// Sets a prefix written verbatim at the start of every failure message, after the location.
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+/// Until this is called, messages have no prefix.
+///
+/// ```rust
+/// unwrap_log::set_message_prefix("[billing] ");
+/// assert_eq!(unwrap_log::message_prefix(), Some("[billing] "));
+/// // Failures now log as, e.g., `src/main.rs:8:23 [billing] encountered `None``.
+/// ```
+pub fn set_message_prefix(prefix: &'static str) {
+    PREFIX.set(Some(prefix));
+}
+
+/// Removes the prefix set by [`set_message_prefix`], if any.
+pub fn clear_message_prefix() {
+    PREFIX.set(None);
+}
+
+/// Returns the prefix set by [`set_message_prefix`], if any.
+pub fn message_prefix() -> Option<&'static str> {
+    PREFIX.get()
+}
+
+/// The wording of the most common failure messages, for localization.
+///
+/// Install one with [`set_messages`]. Each is written after the location and any
+/// [prefix](set_message_prefix) or `msg`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Messages {
+    /// Written for a `None`, in place of ``encountered `None` ``.
+    pub none: &'static str,
+    /// Written for an `Err`, in place of ``encountered `Err({})` ``. The first `{}` is replaced
+    /// with the error.
+    pub err: &'static str,
+    /// Written for an `Err` which isn't formatted, in place of ``encountered `Err(_)` ``.
+    pub err_no_dbg: &'static str,
+}
+
+impl Messages {
+    /// The messages used until [`set_messages`] is called.
+    pub const ENGLISH: Self = Self {
+        none: "encountered `None`",
+        err: "encountered `Err({})`",
+        err_no_dbg: "encountered `Err(_)`",
+    };
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// Replaces the wording of the most common failure messages.
+///
+/// This is process-global, and affects all call sites, including those in other crates. Other
+/// failures, such as ``took from `None` ``, keep their English wording, as do the messages of
+/// `defmt`, whose format strings are fixed at compile time, and `Err` messages with `kv`, which
+/// carry the error in a field rather than the message.
+///
+/// ```rust
+/// use unwrap_log::{Messages, OptionExt, ResultExt};
+///
+/// unwrap_log::set_messages(Messages {
+///     none: "`None` rencontré",
+///     err: "`Err({})` rencontré",
+///     err_no_dbg: "`Err(_)` rencontré",
+/// });
+/// // Failures now log as, e.g., `src/main.rs:8:23 `Err("oops")` rencontré`.
+/// let x: i32 = Err("oops").unwrap_or_default_log();
+/// ```
+pub fn set_messages(messages: Messages) {
+    MESSAGES.set(messages);
+}
+
+/// Strips `prefix` from the file paths of logged locations, such as a build directory.
+///
+/// This is process-global, and affects all call sites, including those in other crates. Paths
+/// which don't start with `prefix` are logged unchanged, as is the `Location` passed to a
+/// [`Sink`] or hook.
+///
+/// ```rust
+/// unwrap_log::strip_path_prefix("/build/");
+/// // Failures at `/build/src/main.rs` now log as `src/main.rs:8:23 encountered `None``.
+/// ```
+pub fn strip_path_prefix(prefix: &'static str) {
+    PATH_PREFIX.set(Some(prefix));
+}
+
+/// Returns the number of failed unwraps since startup, or since the last [`reset_failure_count`].
+///
+/// This is process-global, and counts failures at all call sites, including those whose
+/// logging was suppressed. It is only available on targets with 64-bit atomics.
+///
+/// ```rust
+/// use unwrap_log::{failure_count, OptionExt};
+///
+/// let before = failure_count();
+/// let _: i32 = None.unwrap_or_default_log();
+/// assert_eq!(failure_count(), before + 1);
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub fn failure_count() -> u64 {
+    FAILURE_COUNT.load(Ordering::Relaxed)
+}
+
+/// Resets the count returned by [`failure_count`] to zero.
+#[cfg(target_has_atomic = "64")]
+pub fn reset_failure_count() {
+    FAILURE_COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Returns the most recent error which was defaulted, formatted with `Debug`.
+///
+/// This is process-global, and is updated at all call sites, including those whose logging was
+/// muted or rate limited, so a handler can default inline and still report the underlying error
+/// at the end. Only the errors of [`ResultExt`] and `ResultExtSource` are recorded; the
+/// alternatives which avoid `Debug`, such as [`ResultExtNoDbg`] and [`ResultExtDisplay`], leave
+/// it unchanged and never allocate, as do the `_as` methods at a level compiled out of `log`.
+/// This is only available with the `alloc` feature.
+///
+/// ```rust
+/// use unwrap_log::ResultExt;
+///
+/// let x: i32 = Err("oops").unwrap_or_default_log();
+/// assert_eq!(unwrap_log::last_error().as_deref(), Some("\"oops\""));
+///
+/// unwrap_log::clear_last_error();
+/// assert_eq!(unwrap_log::last_error(), None);
+///
+/// let guard = unwrap_log::mute();
+/// let x: i32 = Err("muted").unwrap_or_default_log();
+/// drop(guard);
+/// assert_eq!(unwrap_log::last_error().as_deref(), Some("\"muted\""));
+/// ```
+///
+/// An error whose `Debug` impl panics leaves the record as it was:
+///
+/// ```rust
+/// use core::fmt;
+/// use unwrap_log::ResultExt;
+///
+/// struct Panics;
+///
+/// impl fmt::Debug for Panics {
+///     fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         panic!("unformattable")
+///     }
+/// }
+///
+/// let x: i32 = Err("oops").unwrap_or_default_log();
+/// assert!(std::panic::catch_unwind(|| Err::<i32, _>(Panics).unwrap_or_default_log()).is_err());
+/// assert_eq!(unwrap_log::last_error().as_deref(), Some("\"oops\""));
+///
+/// let y = std::thread::spawn(|| Err::<i32, _>("again").unwrap_or_default_log());
+/// assert_eq!(y.join().unwrap(), 0);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn last_error() -> Option<alloc::string::String> {
+    LAST_ERROR.with(|last| last.clone())
+}
+
+/// Forgets the error returned by [`last_error`].
+#[cfg(feature = "alloc")]
+pub fn clear_last_error() {
+    LAST_ERROR.with(|last| *last = None);
+}
+
+/// Records `err` as the [`last_error`].
+///
+/// The error is formatted before taking the lock, since its `Debug` impl may panic, or fail an
+/// unwrap of its own.
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(feature = "defmt", not(feature = "std")), allow(dead_code))]
+fn set_last_error(err: core::fmt::Arguments<'_>) {
+    let err = alloc::fmt::format(err);
+    // Drops the previous error after releasing the lock.
+    let previous = LAST_ERROR.with(|last| last.replace(err));
+    drop(previous);
+}
+
+/// Sets how many failed unwraps are tolerated before the next one panics, or `None` to never panic.
+///
+/// This is process-global, and compares against [`failure_count`], so [`reset_failure_count`]
+/// also restarts the tally. It is intended for tests, to turn frequent silent failures into a hard
+/// error. It is only available on targets with 64-bit atomics.
+///
+/// ```rust,should_panic
+/// use unwrap_log::{set_panic_threshold, OptionExt};
+///
+/// set_panic_threshold(Some(2));
+/// for _ in 0..3 {
+///     let _: i32 = None.unwrap_or_default_log();
+/// }
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub fn set_panic_threshold(threshold: Option<u64>) {
+    PANIC_THRESHOLD.store(threshold.unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+/// The kind of failure passed to a hook installed with [`set_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FailureKind {
+    /// An `Option` was `None`.
+    None,
+    /// A `Result` was `Err`, and the error could be formatted.
+    Err,
+    /// A `Result` was `Err`, and the error was not formatted, as with `ResultExtNoDbg`.
+    ErrNoDbg,
+    /// A `Result` was unexpectedly `Ok`, as with `ResultErrExt`.
+    Ok,
+    /// An `Option` was `Some`, but rejected by a predicate, as with `OptionExt::filter_log`.
+    FilteredOut,
+    /// An `Option` was unexpectedly `Some`, as with `OptionExt::xor_log` or `OptionMutExt::replace_log`.
+    Some,
+}
+
+/// Installs a hook which is called on every reported failure, in place of logging.
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+///
+/// The hook is not called for failures which are filtered out before they would be logged:
+/// those skipped by the rate-limited methods, such as `unwrap_or_default_log_once`, repeats
+/// skipped by `set_dedup` with the `alloc` feature, and those [muted](mute). Every failure is
+/// counted by [`failure_count`] regardless.
+///
+/// ```rust
+/// use core::panic::Location;
+/// use unwrap_log::{FailureKind, OptionExt};
+///
+/// fn hook(location: &Location<'_>, kind: FailureKind) {
+///     assert_eq!(kind, FailureKind::None);
+/// }
+///
+/// unwrap_log::set_hook(hook);
+/// let x: i32 = None.unwrap_or_default_log();
+/// ```
+pub fn set_hook(hook: fn(&Location<'_>, FailureKind)) {
+    HOOK.store(hook as *mut (), Ordering::Relaxed);
+}
+
+/// Removes the hook installed with [`set_hook`], if any, restoring the default logging behavior.
+pub fn clear_hook() {
+    HOOK.store(core::ptr::null_mut(), Ordering::Relaxed);
+}
+
+/// The longest line, in bytes, passed to the [fallback](set_fallback). Longer lines are truncated.
+#[cfg(feature = "fallback")]
+pub const FALLBACK_LEN: usize = 256;
+
+/// Installs a function which receives each failure as a line of text while no `log` logger is
+/// initialized, rather than dropping it.
+///
+/// `log` drops every record until a logger sets its max level, which leaves early boot
+/// unobserved. While [`log::max_level`] is still `Off`, the line that would have been logged is
+/// formatted into a fixed buffer of [`FALLBACK_LEN`] bytes, without allocating, and passed to
+/// `fallback`. Without a fallback, such failures are dropped as before. This is only available
+/// with the `fallback` feature.
+///
+/// ```rust
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use unwrap_log::OptionExt;
+///
+/// static SEEN: AtomicBool = AtomicBool::new(false);
+///
+/// fn early_boot(line: &str) {
+///     if line.contains("encountered `None`") {
+///         SEEN.store(true, Ordering::Relaxed);
+///     }
+/// }
+///
+/// unwrap_log::set_fallback(early_boot);
+/// let x: i32 = None.unwrap_or_default_log();
+/// assert!(SEEN.load(Ordering::Relaxed));
+/// ```
+#[cfg(feature = "fallback")]
+pub fn set_fallback(fallback: fn(&str)) {
+    FALLBACK.set(Some(fallback));
+}
+
+/// Removes the fallback installed with [`set_fallback`], if any.
+#[cfg(feature = "fallback")]
+pub fn clear_fallback() {
+    FALLBACK.set(None);
+}
+
+fn hook() -> Option<fn(&Location<'_>, FailureKind)> {
+    let hook = HOOK.load(Ordering::Relaxed);
+    // SAFETY: the only non-null values ever stored are `fn(&Location<'_>, FailureKind)` pointers.
+    (!hook.is_null())
+        .then(|| unsafe { core::mem::transmute::<*mut (), fn(&Location<'_>, FailureKind)>(hook) })
+}
+
+/// Extension trait providing tracing alternatives to `Option` unwrap methods.
+///
+/// Only the `_default_` methods require `Default`, so the others work for any `T`, including
+/// trait objects:
+///
+/// ```rust
+/// use core::fmt::Debug;
+/// use unwrap_log::OptionExt;
+///
+/// let missing: Option<Box<dyn Debug>> = None;
+/// let x = missing.unwrap_or_else_log(|| Box::new("fallback"));
+/// assert_eq!(format!("{x:?}"), "\"fallback\"");
+///
+/// let missing: Option<Box<dyn Debug>> = None;
+/// let y = missing.unwrap_or_log(Box::new(0));
+/// assert_eq!(format!("{y:?}"), "0");
+/// ```
+pub trait OptionExt {
+    /// The type of the "present" output, intended to be `T` for a `Option<T>`.
+    type Output;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_default_log`](OptionExt::unwrap_or_default_log), but includes the name of
+    /// the contained type in the message, as in ``encountered `None::<u8>` ``.
+    fn unwrap_or_default_log_typed(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but also returns whether the default was substituted.
+    fn unwrap_or_default_log_flagged(self) -> (Self::Output, bool)
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but also returns the failure as it was logged, such as
+    /// ``src/main.rs:8:23 encountered `None` ``, if the default was substituted.
+    ///
+    /// This is `None` if the failure was not logged at all: if a [hook](set_hook) was installed,
+    /// or if it was [muted](mute), deduplicated, or turned off by `init_from_env`.
+    ///
+    /// This is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn unwrap_or_default_log_captured(self) -> (Self::Output, Option<alloc::string::String>)
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but emits `log` records with `target`, in place of the
+    /// [global target](set_target).
+    ///
+    /// See [`unwrap_or_default_log_in!`]. Other backends ignore the target.
+    fn unwrap_or_default_log_in(self, target: &'static str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but attaches `key = value` as a structured field alongside
+    /// `location`.
+    ///
+    /// This is only available with the `kv` feature, and the field is only emitted through `log`.
+    #[cfg(feature = "kv")]
+    fn unwrap_or_default_log_kv(self, key: &'static str, value: &str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but wraps the value in a [`LoggedDefault`] recording whether
+    /// it was substituted.
+    fn unwrap_or_default_logged(self) -> LoggedDefault<Self::Output>
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but returns a [`Logged`] which must be passed through a
+    /// metric hook with [`or_metric`](Logged::or_metric).
+    fn unwrap_or_default_log_metered(self) -> Logged<Self::Output>
+    where
+        Self::Output: Default;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log(self, default: Self::Output) -> Self::Output;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and returns the provided default, converted with `Into`.
+    ///
+    /// This suits `Cow` defaults, which a `'static` borrow converts into without allocating,
+    /// while a present owned value passes through untouched:
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use unwrap_log::OptionExt;
+    ///
+    /// let missing: Option<Cow<'static, str>> = None;
+    /// let name = missing.unwrap_or_log_into("anonymous");
+    /// assert!(matches!(name, Cow::Borrowed("anonymous")));
+    ///
+    /// let present: Option<Cow<'static, str>> = Some(Cow::Owned(String::from("ferris")));
+    /// let name = present.unwrap_or_log_into("anonymous");
+    /// assert!(matches!(name, Cow::Owned(ref s) if s == "ferris"));
+    /// ```
+    fn unwrap_or_log_into(self, default: impl Into<Self::Output>) -> Self::Output;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and returns a copy of `default`.
+    ///
+    /// This suits defaults that differ from the type's `Default`, kept in a `const` and borrowed
+    /// at each use rather than moved:
+    ///
+    /// ```rust
+    /// use unwrap_log::{Level, OptionExt};
+    ///
+    /// const FALLBACK: Level = Level::Warn;
+    ///
+    /// let levels = [Some(Level::Error), None, Some(Level::Debug)];
+    /// let mut resolved = [Level::Trace; 3];
+    /// for (level, out) in levels.into_iter().zip(&mut resolved) {
+    ///     *out = level.unwrap_or_log_const(&FALLBACK);
+    /// }
+    /// assert_eq!(resolved, [Level::Error, Level::Warn, Level::Debug]);
+    ///
+    /// assert_eq!(None.unwrap_or_log_const(&'?'), '?');
+    /// assert!(!Some(false).unwrap_or_log_const(&true));
+    /// ```
+    fn unwrap_or_log_const(self, default: &Self::Output) -> Self::Output
+    where
+        Self::Output: Copy;
+    /// Like [`unwrap_or_default_log`](Self::unwrap_or_default_log), but also logs the contained
+    /// value at `trace` when it is present, as in ``resolved `Some(1)` ``.
+    ///
+    /// Successes are not counted or passed to the [hook](set_hook), but they do reach the
+    /// [sink](set_sink) if one is installed.
+    fn unwrap_or_default_log_verbose(self) -> Self::Output
+    where
+        Self::Output: Default + PayloadFormat;
+    /// Like [`unwrap_or_else_log`](Self::unwrap_or_else_log), but also logs the contained value at `trace` when it is present.
+    fn unwrap_or_else_log_verbose(self, f: impl FnOnce() -> Self::Output) -> Self::Output
+    where
+        Self::Output: PayloadFormat;
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), but also logs the contained value at `trace` when it is present.
+    fn unwrap_or_log_verbose(self, default: Self::Output) -> Self::Output
+    where
+        Self::Output: PayloadFormat;
+    /// Returns the contained `Some` value, or logs at the given level and returns a default value.
+    ///
+    #[cfg_attr(feature = "log", doc = "```rust")]
+    #[cfg_attr(not(feature = "log"), doc = "```rust,ignore")]
+    /// use unwrap_log::{Level, OptionExt};
+    ///
+    /// let x: i32 = None.unwrap_or_default_log_at(Level::Error);
+    /// let y: i32 = None.unwrap_or_default_log_at(log::Level::Error);
+    /// assert_eq!((x, y), (0, 0));
+    /// ```
+    fn unwrap_or_default_log_at(self, level: impl Into<Level>) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Some` value, or logs at the given level and computes a default value from a closure.
+    fn unwrap_or_else_log_at(
+        self,
+        level: impl Into<Level>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Some` value, or logs at the given level and returns the provided default.
+    fn unwrap_or_log_at(self, level: impl Into<Level>, default: Self::Output) -> Self::Output;
+    /// Like [`unwrap_or_default_log_at`](Self::unwrap_or_default_log_at), but at a level fixed at
+    /// compile time, which `log` may [compile out](level::StaticLevel).
+    ///
+    /// ```rust
+    /// use unwrap_log::level::Error;
+    /// use unwrap_log::OptionExt;
+    ///
+    /// let x: i32 = None.unwrap_or_default_log_as::<Error>();
+    /// assert_eq!(x, 0);
+    /// ```
+    fn unwrap_or_default_log_as<L: StaticLevel>(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log_at`](Self::unwrap_or_else_log_at), but at a level fixed at
+    /// compile time.
+    fn unwrap_or_else_log_as<L: StaticLevel>(
+        self,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log_at`](Self::unwrap_or_log_at), but at a level fixed at compile time.
+    fn unwrap_or_log_as<L: StaticLevel>(self, default: Self::Output) -> Self::Output;
+    /// Like [`unwrap_or_default_log`](Self::unwrap_or_default_log), but reports `location` rather
+    /// than the caller, for wrappers which forward their own caller's location.
+    fn unwrap_or_default_log_at_location(
+        self,
+        location: &'static Location<'static>,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log`](Self::unwrap_or_else_log), but reports `location` rather than the caller.
+    fn unwrap_or_else_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), but reports `location` rather than the caller.
+    fn unwrap_or_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        default: Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Some` value, or logs `msg` at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_expect_log(self, msg: &str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Some` value, or logs `msg` at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_expect_log(self, msg: &str, f: impl FnOnce() -> Self::Output)
+        -> Self::Output;
+    /// Returns the contained `Some` value, or logs `msg` at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_expect_log(self, msg: &str, default: Self::Output) -> Self::Output;
+    /// Logs at the [default level](default_level) if `None`, and returns `self` unchanged.
+    #[must_use]
+    fn inspect_none_log(self) -> Self;
+    /// Logs the contained value at `level` if `Some`, and returns `self` unchanged.
+    ///
+    /// Like the `_verbose` methods, this bypasses the [hook](set_hook) and [`failure_count`].
+    #[must_use]
+    fn inspect_some_log(self, level: impl Into<Level>) -> Self
+    where
+        Self::Output: PayloadFormat;
+    /// Logs at the [default level](default_level) and then calls `f` if `None`, and returns `self` unchanged.
+    #[must_use]
+    fn inspect_none_with(self, f: impl FnOnce()) -> Self;
+    /// Converts to a `Result`, logging at the [default level](default_level) and returning `Err(err)` if `None`.
+    fn ok_or_log<E>(self, err: E) -> Result<Self::Output, E>;
+    /// Converts to a `Result`, logging at the [default level](default_level) and computing an error from a closure if `None`.
+    fn ok_or_else_log<E>(self, err: impl FnOnce() -> E) -> Result<Self::Output, E>;
+    /// Returns `None` if `None`, or if the predicate rejects the contained value, in which case it
+    /// is logged at the [default level](default_level).
+    fn filter_log(self, predicate: impl FnOnce(&Self::Output) -> bool) -> Self
+    where
+        Self::Output: PayloadFormat;
+    /// Applies `f` to the contained value, or logs at the [default level](default_level) and returns the provided default.
+    fn map_or_log<U>(self, default: U, f: impl FnOnce(Self::Output) -> U) -> U;
+    /// Applies `f` to the contained value, or logs at the [default level](default_level) and computes a default from a closure.
+    fn map_or_else_log<U>(
+        self,
+        default: impl FnOnce() -> U,
+        f: impl FnOnce(Self::Output) -> U,
+    ) -> U;
+    /// Calls `f` with the contained value, or logs at the [default level](default_level) and returns `None`.
+    fn and_then_log<U>(self, f: impl FnOnce(Self::Output) -> Option<U>) -> Option<U>;
+    /// Returns `other` if `self` is `Some`, or logs at the [default level](default_level) and returns `None`.
+    fn and_log<U>(self, other: Option<U>) -> Option<U>;
+    /// Returns `Continue` with the contained value, or logs at the [default level](default_level)
+    /// and returns `Break`.
+    ///
+    /// See [`log_try!`] for an early return built on this.
+    fn log_or_break(self) -> ControlFlow<(), Self::Output>;
+    /// Returns `self` if `Some`, or logs at the [default level](default_level) and calls `f`.
+    fn or_else_log(self, f: impl FnOnce() -> Option<Self::Output>) -> Option<Self::Output>;
+    /// Zips `self` with `other`, logging at the [default level](default_level) which of the two
+    /// was `None`, if either.
+    fn zip_log<U>(self, other: Option<U>) -> Option<(Self::Output, U)>;
+    /// Combines `self` and `other` with `f`, logging at the [default level](default_level) which
+    /// of the two was `None`, if either.
+    fn zip_with_log<U, R>(
+        self,
+        other: Option<U>,
+        f: impl FnOnce(Self::Output, U) -> R,
+    ) -> Option<R>;
+    /// Returns `Some` if exactly one of `self` and `other` is `Some`, or logs at the
+    /// [default level](default_level) whether both were `Some` or both were `None`.
+    fn xor_log(self, other: Option<Self::Output>) -> Option<Self::Output>;
+    /// Returns the contained `Some` value, or returns a default value, logging at the
+    /// [default level](default_level) only if `logged` was not yet set.
+    ///
+    /// See [`unwrap_or_default_log_once!`] for a per-call-site flag.
+    fn unwrap_or_default_log_once(self, logged: &AtomicBool) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but tallies failures in `count`, and includes the tally in the
+    /// message, as in ``encountered `None` (x3)``, and as an `occurrence` field with `kv` or `tracing`.
+    ///
+    /// See [`unwrap_or_default_log_counted!`] for a per-call-site tally, or `set_dedup` with the
+    /// `alloc` feature for a tally keyed by location.
+    fn unwrap_or_default_log_counted(self, count: &AtomicU32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Some` value, or returns a default value, logging at the
+    /// [default level](default_level) only every `n`th time, as tallied by `count`.
+    ///
+    /// With a nonzero `n`, the first failure is always logged; an `n` of zero never logs.
+    /// See [`unwrap_or_default_log_sampled!`] for a per-call-site tally.
+    fn unwrap_or_default_log_sampled(self, count: &AtomicU32, n: u32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Some` value, or returns a default value, logging at the
+    /// [default level](default_level) at most once every `interval_ms` milliseconds.
+    ///
+    /// `next` holds the earliest time at which the next failure may be logged, according to the
+    /// [installed clock](set_clock). Without a clock, every failure is logged.
+    /// See [`unwrap_or_default_log_throttled!`] for a per-call-site timestamp.
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled(self, next: &AtomicU64, interval_ms: u64) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained value, or reports the failure from the `site` of a macro.
+    #[doc(hidden)]
+    fn __log_at_site(self, site: Site<'_>) -> Option<Self::Output>;
+}
+
+/// Extension trait providing tracing alternatives to `Option` methods which take `&mut self`.
+pub trait OptionMutExt {
+    /// The type of the "present" output, intended to be `T` for a `Option<T>`.
+    type Output;
+    /// Inserts `value` if `None`, logging at the [default level](default_level), then returns a mutable reference to the contained value.
+    fn get_or_insert_log(&mut self, value: Self::Output) -> &mut Self::Output;
+    /// Inserts a value computed from a closure if `None`, logging at the [default level](default_level), then returns a mutable reference to the contained value.
+    ///
+    /// The insertion is logged at the caller's location. An `Option` doesn't record whether it
+    /// held a value before, so this can't tell first-time initialization from re-initialization
+    /// after a `take`. To find unexpected re-initialization, also log the takes with
+    /// [`take_log`](OptionMutExt::take_log):
+    ///
+    /// ```rust
+    /// use unwrap_log::OptionMutExt;
+    ///
+    /// let mut cache: Option<Vec<u8>> = None;
+    /// cache.get_or_insert_with_log(Vec::new).push(1);
+    /// cache.get_or_insert_with_log(Vec::new).push(2);
+    /// assert_eq!(cache.as_deref(), Some(&[1, 2][..]));
+    /// ```
+    fn get_or_insert_with_log(&mut self, f: impl FnOnce() -> Self::Output) -> &mut Self::Output;
+    /// Takes the value out, logging at the [default level](default_level) if it was already `None`.
+    fn take_log(&mut self) -> Option<Self::Output>;
+    /// Replaces the value with `value`, logging at the [default level](default_level) if this
+    /// overwrote a `Some`, and returns the old value.
+    fn replace_log(&mut self, value: Self::Output) -> Option<Self::Output>;
+}
+
+/// Extension trait providing a tracing alternative to `Option::flatten`.
+pub trait OptionFlattenExt {
+    /// The type of the "successful" output, intended to be `T` for an `Option<Option<T>>`.
+    type Output;
+    /// Flattens one level of nesting, logging at the [default level](default_level) whether the
+    /// outer or the inner `Option` was `None`.
+    fn flatten_log(self) -> Option<Self::Output>;
+}
+
+/// Extension trait providing a tracing alternative to `Option::transpose`.
+///
+/// ```rust
+/// use unwrap_log::OptionTransposeExt;
+///
+/// let x: Option<Result<u16, _>> = Some("http".parse());
+/// assert!(x.transpose_log().is_err());
+///
+/// let y: Option<Result<u16, ()>> = None;
+/// assert_eq!(y.transpose_log(), Ok(None));
+/// ```
+pub trait OptionTransposeExt {
+    /// The type of the "successful" output, intended to be `T` for an `Option<Result<T, E>>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for an `Option<Result<T, E>>`.
+    type Error;
+    /// Transposes into a `Result` of an `Option`, logging at the [default level](default_level)
+    /// if it holds an `Err`.
+    fn transpose_log(self) -> Result<Option<Self::Output>, Self::Error>;
+}
+
+/// Extension trait providing a tracing alternative to `Option::unwrap_or_default` for arrays of
+/// any length.
+///
+/// `[T; N]` only implements `Default` for `N <= 32`, so larger arrays can't use
+/// [`unwrap_or_default_log`](OptionExt::unwrap_or_default_log).
+///
+/// ```rust
+/// use unwrap_log::OptionArrayExt;
+///
+/// let x: [u8; 64] = None.unwrap_or_default_log_array();
+/// assert_eq!(x, [0; 64]);
+/// ```
+pub trait OptionArrayExt<T, const N: usize> {
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and
+    /// returns an array of default elements.
+    fn unwrap_or_default_log_array(self) -> [T; N]
+    where
+        T: Default;
+}
+
+/// Extension trait providing a tracing alternative to `Option::unwrap_or_default` for the
+/// `NonZero*` integers.
+///
+/// The `NonZero*` integers don't implement `Default`, so this returns one instead.
+///
+/// ```rust
+/// use core::num::{NonZeroI32, NonZeroU32};
+/// use unwrap_log::OptionNonZeroExt;
+///
+/// let x: Option<NonZeroU32> = None;
+/// assert_eq!(x.unwrap_or_one_log().get(), 1);
+///
+/// let y = NonZeroI32::new(-7);
+/// assert_eq!(y.unwrap_or_one_log().get(), -7);
+/// ```
+///
+/// This is implemented for every width:
+///
+/// ```rust
+/// use core::num::*;
+/// use unwrap_log::OptionNonZeroExt;
+///
+/// assert_eq!(None::<NonZeroU8>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroU16>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroU32>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroU64>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroU128>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroUsize>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroI8>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroI16>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroI32>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroI64>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroI128>.unwrap_or_one_log().get(), 1);
+/// assert_eq!(None::<NonZeroIsize>.unwrap_or_one_log().get(), 1);
+/// ```
+pub trait OptionNonZeroExt {
+    /// The type of the "successful" output, intended to be `T` for an `Option<T>`.
+    type Output;
+    /// Returns the contained `Some` value, or logs at the [default level](default_level) and
+    /// returns one.
+    fn unwrap_or_one_log(self) -> Self::Output;
+}
+
+/// Extension trait providing a tracing alternative to `Option::as_deref`.
+///
+/// ```rust
+/// use unwrap_log::OptionDerefExt;
+///
+/// let name: Option<String> = None;
+/// assert_eq!(name.as_deref_log(), None);
+///
+/// let name = Some(String::from("ferris"));
+/// assert_eq!(name.as_deref_log(), Some("ferris"));
+/// ```
+pub trait OptionDerefExt {
+    /// The type dereferenced to, intended to be `T::Target` for an `Option<T>`.
+    type Target: ?Sized;
+    /// Dereferences the contained `Some` value, or logs at the [default level](default_level) and returns `None`.
+    fn as_deref_log(&self) -> Option<&Self::Target>;
+}
+
+/// Extension trait providing a tracing alternative to `Option::as_ref` followed by `unwrap_or`.
+///
+/// ```rust
+/// use unwrap_log::OptionRefExt;
+///
+/// let fallback = String::from("anonymous");
+/// let name: Option<String> = None;
+/// assert_eq!(name.as_ref_or_log(&fallback), "anonymous");
+/// ```
+pub trait OptionRefExt {
+    /// The type of the "present" output, intended to be `T` for an `Option<T>`.
+    type Output;
+    /// Returns a reference to the contained `Some` value, or logs at the
+    /// [default level](default_level) and returns `default`.
+    fn as_ref_or_log<'a>(&'a self, default: &'a Self::Output) -> &'a Self::Output;
+}
+
+/// Extension trait providing a tracing alternative to `Result::as_ref` followed by `unwrap_or`.
+///
+/// ```rust
+/// use unwrap_log::ResultRefExt;
+///
+/// let fallback = String::from("anonymous");
+/// let name: Result<String, &str> = Err("unset");
+/// assert_eq!(name.as_ref_or_log(&fallback), "anonymous");
+/// ```
+pub trait ResultRefExt {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// Returns a reference to the contained `Ok` value, or logs at the
+    /// [default level](default_level) and returns `default`.
+    fn as_ref_or_log<'a>(&'a self, default: &'a Self::Output) -> &'a Self::Output;
+}
+
+/// Extension trait providing tracing alternatives to `Result` unwrap methods.
+pub trait ResultExt {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_default_log`](ResultExt::unwrap_or_default_log), but includes the names of
+    /// the contained types in the message, as in ``encountered `Err::<u8, &str>("oops")` ``.
+    fn unwrap_or_default_log_typed(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but also returns whether the default was substituted.
+    fn unwrap_or_default_log_flagged(self) -> (Self::Output, bool)
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but also returns the failure as it was logged, such as
+    /// ``src/main.rs:8:23 encountered `None` ``, if the default was substituted.
+    ///
+    /// This is `None` if the failure was not logged at all: if a [hook](set_hook) was installed,
+    /// or if it was [muted](mute), deduplicated, or turned off by `init_from_env`.
+    ///
+    /// This is only available with the `alloc` feature.
+    ///
+    /// ```rust
+    /// use unwrap_log::ResultExt;
+    ///
+    /// let (x, logged): (i32, _) = Err("oops").unwrap_or_default_log_captured();
+    /// assert_eq!(x, 0);
+    /// assert!(logged.unwrap().ends_with("encountered `Err(\"oops\")`"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn unwrap_or_default_log_captured(self) -> (Self::Output, Option<alloc::string::String>)
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but emits `log` records with `target`, in place of the
+    /// [global target](set_target).
+    ///
+    /// See [`unwrap_or_default_log_in!`]. Other backends ignore the target.
+    fn unwrap_or_default_log_in(self, target: &'static str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but attaches `key = value` as a structured field alongside
+    /// `location`.
+    ///
+    /// This is only available with the `kv` feature, and the field is only emitted through `log`.
+    #[cfg(feature = "kv")]
+    fn unwrap_or_default_log_kv(self, key: &'static str, value: &str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but wraps the value in a [`LoggedDefault`] recording whether
+    /// it was substituted.
+    fn unwrap_or_default_logged(self) -> LoggedDefault<Self::Output>
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but returns a [`Logged`] which must be passed through a
+    /// metric hook with [`or_metric`](Logged::or_metric).
+    fn unwrap_or_default_log_metered(self) -> Logged<Self::Output>
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from the error.
+    fn unwrap_or_else_log_with(self, f: impl FnOnce(Self::Error) -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a reference to the error.
+    fn unwrap_or_else_log_ref(self, f: impl FnOnce(&Self::Error) -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log(self, default: Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default, converted with `Into`.
+    fn unwrap_or_log_into(self, default: impl Into<Self::Output>) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a copy of `default`.
+    ///
+    /// ```rust
+    /// use unwrap_log::ResultExt;
+    ///
+    /// const PORT: u16 = 8080;
+    ///
+    /// let ports: Vec<u16> = ["80", "http", "443"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap_or_log_const(&PORT))
+    ///     .collect();
+    /// assert_eq!(ports, [80, 8080, 443]);
+    ///
+    /// assert_eq!("-1".parse::<u32>().unwrap_or_log_const(&u32::MAX), u32::MAX);
+    /// assert_eq!("2.5".parse::<f64>().unwrap_or_log_const(&0.0), 2.5);
+    /// ```
+    fn unwrap_or_log_const(self, default: &Self::Output) -> Self::Output
+    where
+        Self::Output: Copy;
+    /// Like [`unwrap_or_default_log`](Self::unwrap_or_default_log), but also logs the contained
+    /// value at `trace` when it is present, as in ``resolved `Ok(1)` ``.
+    ///
+    /// Successes are not counted or passed to the [hook](set_hook), but they do reach the
+    /// [sink](set_sink) if one is installed.
+    fn unwrap_or_default_log_verbose(self) -> Self::Output
+    where
+        Self::Output: Default + PayloadFormat;
+    /// Like [`unwrap_or_else_log`](Self::unwrap_or_else_log), but also logs the contained value at `trace` when it is present.
+    fn unwrap_or_else_log_verbose(self, f: impl FnOnce() -> Self::Output) -> Self::Output
+    where
+        Self::Output: PayloadFormat;
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), but also logs the contained value at `trace` when it is present.
+    fn unwrap_or_log_verbose(self, default: Self::Output) -> Self::Output
+    where
+        Self::Output: PayloadFormat;
+    /// Returns the contained `Ok` value, or logs at the given level and returns a default value.
+    fn unwrap_or_default_log_at(self, level: impl Into<Level>) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the level chosen by `level_of` from the error and
+    /// returns a default value.
+    ///
+    /// This lets benign errors be logged at a lower level than fatal ones from the same call site.
+    fn unwrap_or_default_log_leveled<L: Into<Level>>(
+        self,
+        level_of: impl FnOnce(&Self::Error) -> L,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the given level and computes a default value from a closure.
+    fn unwrap_or_else_log_at(
+        self,
+        level: impl Into<Level>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the given level and returns the provided default.
+    fn unwrap_or_log_at(self, level: impl Into<Level>, default: Self::Output) -> Self::Output;
+    /// Like [`unwrap_or_default_log_at`](Self::unwrap_or_default_log_at), but at a level fixed at
+    /// compile time, which `log` may [compile out](level::StaticLevel).
+    fn unwrap_or_default_log_as<L: StaticLevel>(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log_at`](Self::unwrap_or_else_log_at), but at a level fixed at
+    /// compile time.
+    fn unwrap_or_else_log_as<L: StaticLevel>(
+        self,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log_at`](Self::unwrap_or_log_at), but at a level fixed at compile time.
+    fn unwrap_or_log_as<L: StaticLevel>(self, default: Self::Output) -> Self::Output;
+    /// Like [`unwrap_or_default_log`](Self::unwrap_or_default_log), but reports `location` rather
+    /// than the caller, for wrappers which forward their own caller's location.
+    fn unwrap_or_default_log_at_location(
+        self,
+        location: &'static Location<'static>,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log`](Self::unwrap_or_else_log), but reports `location` rather than the caller.
+    fn unwrap_or_else_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), but reports `location` rather than the caller.
+    fn unwrap_or_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        default: Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_expect_log(self, msg: &str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_expect_log(self, msg: &str, f: impl FnOnce() -> Self::Output)
+        -> Self::Output;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_expect_log(self, msg: &str, default: Self::Output) -> Self::Output;
+    /// Logs at the [default level](default_level) if `Err`, and returns `self` unchanged.
+    #[must_use]
+    fn inspect_err_log(self) -> Self;
+    /// Logs the contained value at `level` if `Ok`, and returns `self` unchanged.
+    ///
+    /// Like the `_verbose` methods, this bypasses the [hook](set_hook) and [`failure_count`].
+    #[must_use]
+    fn inspect_ok_log(self, level: impl Into<Level>) -> Self
+    where
+        Self::Output: PayloadFormat;
+    /// Logs at the [default level](default_level) and then calls `f` with the error if `Err`, and returns `self` unchanged.
+    #[must_use]
+    fn inspect_err_with(self, f: impl FnOnce(&Self::Error)) -> Self;
+    /// Converts to an `Option`, logging at the [default level](default_level) if `Err`.
+    fn ok_log(self) -> Option<Self::Output>;
+    /// Logs the original error at the [default level](default_level) if `Err`, then maps it with `op`.
+    fn map_err_log<F>(self, op: impl FnOnce(Self::Error) -> F) -> Result<Self::Output, F>;
+    /// Applies `f` to the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn map_or_log<U>(self, default: U, f: impl FnOnce(Self::Output) -> U) -> U;
+    /// Applies `f` to the contained `Ok` value, or logs at the [default level](default_level) and computes a default from the error.
+    fn map_or_else_log<U>(
+        self,
+        default: impl FnOnce(Self::Error) -> U,
+        f: impl FnOnce(Self::Output) -> U,
+    ) -> U;
+    /// Calls `f` with the contained `Ok` value, or logs at the [default level](default_level) and returns the `Err`.
+    fn and_then_log<U>(
+        self,
+        f: impl FnOnce(Self::Output) -> Result<U, Self::Error>,
+    ) -> Result<U, Self::Error>;
+    /// Returns `other` if `self` is `Ok`, or logs at the [default level](default_level) and returns the `Err`.
+    fn and_log<U>(self, other: Result<U, Self::Error>) -> Result<U, Self::Error>;
+    /// Returns `Continue` with the contained `Ok` value, or logs at the [default level](default_level)
+    /// and returns `Break`.
+    ///
+    /// ```rust
+    /// use core::ops::ControlFlow;
+    /// use unwrap_log::ResultExt;
+    ///
+    /// fn port(s: &str) -> Option<u16> {
+    ///     let ControlFlow::Continue(port) = s.parse().log_or_break() else {
+    ///         return None;
+    ///     };
+    ///     Some(port)
+    /// }
+    ///
+    /// assert_eq!(port("80"), Some(80));
+    /// assert_eq!(port("http"), None);
+    /// ```
+    ///
+    /// See [`log_try!`] for an early return built on this.
+    fn log_or_break(self) -> ControlFlow<(), Self::Output>;
+    /// Returns `self` if `Ok`, or logs the error at the [default level](default_level) and calls `f` with it.
+    fn or_else_log<F>(
+        self,
+        f: impl FnOnce(Self::Error) -> Result<Self::Output, F>,
+    ) -> Result<Self::Output, F>;
+    /// Returns `self` if `Ok`, or logs the error at the [default level](default_level) and tries
+    /// `f`, logging its error too if it fails.
+    ///
+    /// This suits the last of several layered fallbacks. Since each `_log` method logs the error
+    /// it is called on, the earlier layers chain with [`or_else_log`](Self::or_else_log), so
+    /// that each error is logged once:
+    ///
+    /// ```rust
+    /// use unwrap_log::ResultExt;
+    ///
+    /// fn cache() -> Result<u32, &'static str> { Err("cache miss") }
+    /// fn disk() -> Result<u32, &'static str> { Err("file not found") }
+    /// fn network() -> Result<u32, &'static str> { Err("timed out") }
+    ///
+    /// // Logs "cache miss", "file not found" and "timed out".
+    /// let x = cache().or_else_log(|_| disk()).or_try_log(network);
+    /// assert_eq!(x, Err("timed out"));
+    ///
+    /// assert_eq!(disk().or_try_log(|| Ok::<_, &str>(7)), Ok(7));
+    /// ```
+    fn or_try_log<F: PayloadFormat>(
+        self,
+        f: impl FnOnce() -> Result<Self::Output, F>,
+    ) -> Result<Self::Output, F>;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) only if `logged` was not yet set.
+    ///
+    /// See [`unwrap_or_default_log_once!`] for a per-call-site flag.
+    fn unwrap_or_default_log_once(self, logged: &AtomicBool) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log`, but tallies failures in `count`, and includes the tally in the
+    /// message, as in ``encountered `None` (x3)``, and as an `occurrence` field with `kv` or `tracing`.
+    ///
+    /// See [`unwrap_or_default_log_counted!`] for a per-call-site tally, or `set_dedup` with the
+    /// `alloc` feature for a tally keyed by location.
+    fn unwrap_or_default_log_counted(self, count: &AtomicU32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) only every `n`th time, as tallied by `count`.
+    ///
+    /// With a nonzero `n`, the first failure is always logged; an `n` of zero never logs.
+    /// See [`unwrap_or_default_log_sampled!`] for a per-call-site tally.
+    fn unwrap_or_default_log_sampled(self, count: &AtomicU32, n: u32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) at most once every `interval_ms` milliseconds.
+    ///
+    /// `next` holds the earliest time at which the next failure may be logged, according to the
+    /// [installed clock](set_clock). Without a clock, every failure is logged.
+    /// See [`unwrap_or_default_log_throttled!`] for a per-call-site timestamp.
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled(self, next: &AtomicU64, interval_ms: u64) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained value, or reports the failure from the `site` of a macro.
+    #[doc(hidden)]
+    fn __log_at_site(self, site: Site<'_>) -> Option<Self::Output>;
+}
+
+/// Like `ResultExt` for `Result<T, E>`, but doesn't require `E: Debug`.
+///
+/// This is provided for users who want to avoid logging sensitive information,
+/// or who want to slim down their log files.
+///
+/// Each method is named after its `ResultExt` counterpart with a `_no_dbg` suffix, so the two
+/// traits can be imported together.
+pub trait ResultExtNoDbg {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_no_dbg(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like `unwrap_or_default_log_no_dbg`, but also returns whether the default was substituted.
+    fn unwrap_or_default_log_flagged_no_dbg(self) -> (Self::Output, bool)
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_no_dbg(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from the error.
+    fn unwrap_or_else_log_with_no_dbg(
+        self,
+        f: impl FnOnce(Self::Error) -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a reference to the error.
+    fn unwrap_or_else_log_ref_no_dbg(
+        self,
+        f: impl FnOnce(&Self::Error) -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_no_dbg(self, default: Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default, converted with `Into`.
+    fn unwrap_or_log_into_no_dbg(self, default: impl Into<Self::Output>) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the given level and returns a default value.
+    fn unwrap_or_default_log_at_no_dbg(self, level: impl Into<Level>) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the given level and computes a default value from a closure.
+    fn unwrap_or_else_log_at_no_dbg(
+        self,
+        level: impl Into<Level>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the given level and returns the provided default.
+    fn unwrap_or_log_at_no_dbg(
+        self,
+        level: impl Into<Level>,
+        default: Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_default_log_at_no_dbg`](Self::unwrap_or_default_log_at_no_dbg), but at a
+    /// level fixed at compile time, which `log` may [compile out](level::StaticLevel).
+    fn unwrap_or_default_log_as_no_dbg<L: StaticLevel>(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log_at_no_dbg`](Self::unwrap_or_else_log_at_no_dbg), but at a level
+    /// fixed at compile time.
+    fn unwrap_or_else_log_as_no_dbg<L: StaticLevel>(
+        self,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log_at_no_dbg`](Self::unwrap_or_log_at_no_dbg), but at a level fixed at
+    /// compile time.
+    fn unwrap_or_log_as_no_dbg<L: StaticLevel>(self, default: Self::Output) -> Self::Output;
+    /// Like [`unwrap_or_default_log_no_dbg`](Self::unwrap_or_default_log_no_dbg), but reports `location` rather
+    /// than the caller, for wrappers which forward their own caller's location.
+    fn unwrap_or_default_log_at_location_no_dbg(
+        self,
+        location: &'static Location<'static>,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Like [`unwrap_or_else_log_no_dbg`](Self::unwrap_or_else_log_no_dbg), but reports `location` rather than the caller.
+    fn unwrap_or_else_log_at_location_no_dbg(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Like [`unwrap_or_log_no_dbg`](Self::unwrap_or_log_no_dbg), but reports `location` rather than the caller.
+    fn unwrap_or_log_at_location_no_dbg(
+        self,
+        location: &'static Location<'static>,
+        default: Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_expect_log_no_dbg(self, msg: &str) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_expect_log_no_dbg(
+        self,
+        msg: &str,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs `msg` at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_expect_log_no_dbg(self, msg: &str, default: Self::Output) -> Self::Output;
+    /// Logs at the [default level](default_level) if `Err`, and returns `self` unchanged.
+    #[must_use]
+    fn inspect_err_log_no_dbg(self) -> Self;
+    /// Converts to an `Option`, logging at the [default level](default_level) if `Err`.
+    fn ok_log_no_dbg(self) -> Option<Self::Output>;
+    /// Applies `f` to the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn map_or_log_no_dbg<U>(self, default: U, f: impl FnOnce(Self::Output) -> U) -> U;
+    /// Applies `f` to the contained `Ok` value, or logs at the [default level](default_level) and computes a default from the error.
+    fn map_or_else_log_no_dbg<U>(
+        self,
+        default: impl FnOnce(Self::Error) -> U,
+        f: impl FnOnce(Self::Output) -> U,
+    ) -> U;
+    /// Calls `f` with the contained `Ok` value, or logs at the [default level](default_level) and returns the `Err`.
+    fn and_then_log_no_dbg<U>(
+        self,
+        f: impl FnOnce(Self::Output) -> Result<U, Self::Error>,
+    ) -> Result<U, Self::Error>;
+    /// Returns `other` if `self` is `Ok`, or logs at the [default level](default_level) and returns the `Err`.
+    fn and_log_no_dbg<U>(self, other: Result<U, Self::Error>) -> Result<U, Self::Error>;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) only if `logged` was not yet set.
+    ///
+    /// See [`unwrap_or_default_log_once!`] for a per-call-site flag.
+    fn unwrap_or_default_log_once_no_dbg(self, logged: &AtomicBool) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) only every `n`th time, as tallied by `count`.
+    ///
+    /// With a nonzero `n`, the first failure is always logged; an `n` of zero never logs.
+    /// See [`unwrap_or_default_log_sampled!`] for a per-call-site tally.
+    fn unwrap_or_default_log_sampled_no_dbg(self, count: &AtomicU32, n: u32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or returns a default value, logging at the
+    /// [default level](default_level) at most once every `interval_ms` milliseconds.
+    ///
+    /// `next` holds the earliest time at which the next failure may be logged, according to the
+    /// [installed clock](set_clock). Without a clock, every failure is logged.
+    /// See [`unwrap_or_default_log_throttled!`] for a per-call-site timestamp.
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled_no_dbg(
+        self,
+        next: &AtomicU64,
+        interval_ms: u64,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+}
+
+/// Like `ResultExt` for `Result<T, E>`, but logs the error with `Display` rather than `Debug`.
+///
+/// Each method is named after its `ResultExt` counterpart with a `_display` suffix.
+///
+/// The error is formatted directly into the emitted record, so this never allocates, and works
+/// without the `alloc` feature:
+///
+/// ```rust
+/// use std::alloc::{GlobalAlloc, Layout, System};
+/// use std::fmt::Write;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use unwrap_log::ResultExtDisplay;
+///
+/// struct Counting;
+///
+/// static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+///
+/// unsafe impl GlobalAlloc for Counting {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+///         System.alloc(layout)
+///     }
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         System.dealloc(ptr, layout)
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static GLOBAL: Counting = Counting;
+///
+/// /// Formats each record into a fixed buffer, and checks its message.
+/// struct Logger;
+///
+/// struct Buf([u8; 128], usize);
+///
+/// impl Write for Buf {
+///     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+///         let end = self.1 + s.len();
+///         self.0.get_mut(self.1..end).ok_or(std::fmt::Error)?.copy_from_slice(s.as_bytes());
+///         self.1 = end;
+///         Ok(())
+///     }
+/// }
+///
+/// impl log::Log for Logger {
+///     fn enabled(&self, _: &log::Metadata) -> bool {
+///         true
+///     }
+///     fn log(&self, record: &log::Record) {
+///         let mut buf = Buf([0; 128], 0);
+///         write!(buf, "{}", record.args()).unwrap();
+///         assert!(buf.0[..buf.1].ends_with(b"encountered `Err`: oops"));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// log::set_logger(&Logger).unwrap();
+/// log::set_max_level(log::LevelFilter::Warn);
+///
+/// let before = ALLOCATIONS.load(Ordering::Relaxed);
+/// let x: i32 = Err("oops").unwrap_or_default_log_display();
+/// assert_eq!(x, 0);
+/// assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), before);
+/// ```
+pub trait ResultExtDisplay {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_display(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_display(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_display(self, default: Self::Output) -> Self::Output;
+}
+
+/// Like `ResultExt` for `Result<T, E>`, but logs a redacted token in place of the error.
+///
+/// The `redact` closure picks what to log from the error, such as an error code, so that logs stay
+/// useful without leaking sensitive payloads. Each method is named after its `ResultExt`
+/// counterpart with a `_redacted` suffix.
+pub trait ResultExtRedact {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns the contained `Ok` value, or logs the redacted error at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_redacted(
+        self,
+        redact: impl FnOnce(&Self::Error) -> &str,
+    ) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs the redacted error at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_redacted(
+        self,
+        redact: impl FnOnce(&Self::Error) -> &str,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs the redacted error at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_redacted(
+        self,
+        redact: impl FnOnce(&Self::Error) -> &str,
+        default: Self::Output,
+    ) -> Self::Output;
+}
+
+/// Like `ResultExt` for `Result<T, E>`, but logs a numeric code in place of the error.
+///
+/// The `code` closure classifies the error, so that kinds of error can be told apart without
+/// formatting or allocating their payloads, as in ``encountered `Err[kind=42]` ``. For a textual
+/// kind, see [`ResultExtRedact`]. Each method is named after its `ResultExt` counterpart with a
+/// `_code` suffix.
+///
+/// ```rust
+/// use unwrap_log::ResultExtCode;
+///
+/// enum FetchError {
+///     Timeout,
+///     Refused { token: String },
+/// }
+///
+/// fn code(err: &FetchError) -> u32 {
+///     match err {
+///         FetchError::Timeout => 1,
+///         FetchError::Refused { .. } => 2,
+///     }
+/// }
+///
+/// let x: i32 = Err(FetchError::Timeout).unwrap_or_default_log_code(code);
+/// assert_eq!(x, 0);
+/// ```
+pub trait ResultExtCode {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns the contained `Ok` value, or logs the error's code at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_code(self, code: impl FnOnce(&Self::Error) -> u32) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs the error's code at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_code(
+        self,
+        code: impl FnOnce(&Self::Error) -> u32,
+        f: impl FnOnce() -> Self::Output,
+    ) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs the error's code at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_code(
+        self,
+        code: impl FnOnce(&Self::Error) -> u32,
+        default: Self::Output,
+    ) -> Self::Output;
+}
+
+/// Like `ResultExt` for `Result<T, E>`, but also logs each error in the chain of `source`s.
+///
+/// This requires `E: std::error::Error`, and is only available with the `std` feature.
+/// Each method is named after its `ResultExt` counterpart with a `_source` suffix.
+#[cfg(feature = "std")]
+pub trait ResultExtSource {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_source(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_source(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_source(self, default: Self::Output) -> Self::Output;
+}
+
+/// Like `ResultExt` for `Result<T, anyhow::Error>`, but logs the error with its chain of contexts,
+/// as `{:#}` would render it.
+///
+/// This is only available with the `anyhow` feature.
+/// Each method is named after its `ResultExt` counterpart with an `_anyhow` suffix.
+#[cfg(feature = "anyhow")]
+pub trait ResultExtAnyhow {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, anyhow::Error>`.
+    type Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_or_default_log_anyhow(self) -> Self::Output
+    where
+        Self::Output: Default;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_or_else_log_anyhow(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
+    /// Returns the contained `Ok` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_or_log_anyhow(self, default: Self::Output) -> Self::Output;
+}
+
+/// Extension trait providing tracing alternatives to `Result` `unwrap_err` methods.
+pub trait ResultErrExt {
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns the contained `Err` value, or logs at the [default level](default_level) and returns a default value.
+    fn unwrap_err_or_default_log(self) -> Self::Error
+    where
+        Self::Error: Default;
+    /// Returns the contained `Err` value, or logs at the [default level](default_level) and computes a default value from a closure.
+    fn unwrap_err_or_else_log(self, f: impl FnOnce() -> Self::Error) -> Self::Error;
+    /// Returns the contained `Err` value, or logs at the [default level](default_level) and returns the provided default.
+    fn unwrap_err_or_log(self, default: Self::Error) -> Self::Error;
+    /// Returns the contained `Err` value, or logs `msg` at the [default level](default_level) and returns a default value.
+    fn expect_err_or_default_log(self, msg: &str) -> Self::Error
+    where
+        Self::Error: Default;
+}
+
+/// A two-state type which can be converted to its "successful" output, or to a logged default.
+///
+/// This generalizes [`OptionExt::unwrap_or_default_log`] to user-defined types. Implementations
+/// should be `#[track_caller]`, and can usually delegate to the `Option` or `Result` impls:
+///
+/// ```rust
+/// use unwrap_log::{LogDefault, OptionExt};
+///
+/// enum Lookup<T> {
+///     Found(T),
+///     Missing,
+/// }
+///
+/// impl<T: Default> LogDefault for Lookup<T> {
+///     type Output = T;
+///
+///     #[track_caller]
+///     fn into_or_default_log(self) -> T {
+///         match self {
+///             Lookup::Found(x) => Some(x),
+///             Lookup::Missing => None,
+///         }
+///         .unwrap_or_default_log()
+///     }
+/// }
+///
+/// let x: i32 = Lookup::Missing.into_or_default_log();
+/// assert_eq!(x, 0);
+/// ```
+pub trait LogDefault {
+    /// The type of the "successful" output.
+    type Output;
+    /// Returns the "successful" output, or logs at the [default level](default_level) and
+    /// returns a default value.
+    fn into_or_default_log(self) -> Self::Output;
+}
+
+/// Extension trait providing a logging alternative to `TryInto::try_into` followed by
+/// `unwrap_or_default`.
+///
+/// This is implemented for every type, and is mostly useful for numeric narrowing:
+///
+/// ```rust
+/// use unwrap_log::TryIntoExt;
+///
+/// let small: u8 = 42u64.try_into_or_default_log();
+/// assert_eq!(small, 42);
+/// let overflowed: u8 = 300u64.try_into_or_default_log();
+/// assert_eq!(overflowed, 0);
+/// ```
+pub trait TryIntoExt: Sized {
+    /// Converts `self` to `U`, or logs the conversion error at the [default level](default_level)
+    /// and returns a default value.
+    fn try_into_or_default_log<U>(self) -> U
+    where
+        U: TryFrom<Self> + Default,
+        U::Error: PayloadFormat;
+}
+
+/// Extension trait providing a tracing alternative to `Option::unwrap_or_else` with an async fallback.
+///
+/// This is only available with the `async` feature.
+#[cfg(feature = "async")]
+pub trait OptionExtAsync {
+    /// The type of the "successful" output, intended to be `T` for an `Option<T>`.
+    type Output;
+    /// Returns a future of the contained `Some` value, or logs at the [default level](default_level)
+    /// and returns a future of the fallback computed by `f`.
+    ///
+    /// The failure is logged when this is called, before the fallback is awaited.
+    fn unwrap_or_else_log_async<Fut: core::future::Future<Output = Self::Output>>(
+        self,
+        f: impl FnOnce() -> Fut,
+    ) -> impl core::future::Future<Output = Self::Output>;
+}
+
+/// Extension trait providing a tracing alternative to `Result::unwrap_or_else` with an async fallback.
+///
+/// This is only available with the `async` feature.
+#[cfg(feature = "async")]
+pub trait ResultExtAsync {
+    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
+    type Output;
+    /// The type of the "failure" output, intended to be `E` for a `Result<T, E>`.
+    type Error;
+    /// Returns a future of the contained `Ok` value, or logs at the [default level](default_level)
+    /// and returns a future of the fallback computed by `f` from the error.
+    ///
+    /// The failure is logged when this is called, before the fallback is awaited.
+    fn unwrap_or_else_log_async<Fut: core::future::Future<Output = Self::Output>>(
+        self,
+        f: impl FnOnce(Self::Error) -> Fut,
+    ) -> impl core::future::Future<Output = Self::Output>;
+}
+
+impl<T> OptionExt for Option<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn unwrap_or_default_log(self) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_typed(self) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_typed_error(core::any::type_name::<T>());
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_flagged(self) -> (T, bool)
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            (x, false)
+        } else {
+            option_failure();
+            (T::default(), true)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn unwrap_or_default_log_captured(self) -> (T, Option<alloc::string::String>)
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            (x, None)
+        } else {
+            (T::default(), captured_error(Encountered::None))
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_in(self, target: &'static str) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error_in(target);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    #[cfg(feature = "kv")]
+    fn unwrap_or_default_log_kv(self, key: &'static str, value: &str) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error_kv((key, value));
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_logged(self) -> LoggedDefault<T>
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            LoggedDefault::real(x)
+        } else {
+            option_failure();
+            LoggedDefault::defaulted(T::default())
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_metered(self) -> Logged<T>
+    where
+        T: Default,
+    {
+        Logged::new(self.unwrap_or_default_logged())
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log(self, f: impl FnOnce() -> T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log(self, default: T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_into(self, default: impl Into<T>) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            default.into()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_const(self, default: &T) -> T
+    where
+        T: Copy,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            *default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_verbose(self) -> T
+    where
+        T: Default + PayloadFormat,
+    {
+        if let Some(x) = self {
+            resolved(Level::Trace, Resolved::Some(&x));
+            x
+        } else {
+            option_failure();
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_verbose(self, f: impl FnOnce() -> T) -> T
+    where
+        T: PayloadFormat,
+    {
+        if let Some(x) = self {
+            resolved(Level::Trace, Resolved::Some(&x));
+            x
+        } else {
+            option_failure();
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_verbose(self, default: T) -> T
+    where
+        T: PayloadFormat,
+    {
+        if let Some(x) = self {
+            resolved(Level::Trace, Resolved::Some(&x));
+            x
+        } else {
+            option_failure();
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_at(self, level: impl Into<Level>) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(Some(level.into()), None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_at(self, level: impl Into<Level>, f: impl FnOnce() -> T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(Some(level.into()), None);
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_at(self, level: impl Into<Level>, default: T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(Some(level.into()), None);
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_as<L: StaticLevel>(self) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_as<L: StaticLevel>(self, f: impl FnOnce() -> T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::None);
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_as<L: StaticLevel>(self, default: T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::None);
+            default
+        }
+    }
+
+    fn unwrap_or_default_log_at_location(self, location: &'static Location<'static>) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error_at(location, None);
+            T::default()
+        }
+    }
+
+    fn unwrap_or_else_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error_at(location, None);
+            f()
+        }
+    }
+
+    fn unwrap_or_log_at_location(self, location: &'static Location<'static>, default: T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error_at(location, None);
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_expect_log(self, msg: &str) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(None, Some(msg));
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_expect_log(self, msg: &str, f: impl FnOnce() -> T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(None, Some(msg));
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_expect_log(self, msg: &str, default: T) -> T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_error(None, Some(msg));
+            default
+        }
+    }
+
+    #[track_caller]
+    fn inspect_none_log(self) -> Self {
+        if self.is_none() {
+            option_failure();
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_some_log(self, level: impl Into<Level>) -> Self
+    where
+        T: PayloadFormat,
+    {
+        if let Some(x) = &self {
+            resolved(level.into(), Resolved::Some(x));
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_none_with(self, f: impl FnOnce()) -> Self {
+        if self.is_none() {
+            option_failure();
+            f();
+        }
+        self
+    }
+
+    #[track_caller]
+    fn ok_or_log<E>(self, err: E) -> Result<T, E> {
+        if let Some(x) = self {
+            Ok(x)
+        } else {
+            option_failure();
+            Err(err)
+        }
+    }
+
+    #[track_caller]
+    fn ok_or_else_log<E>(self, err: impl FnOnce() -> E) -> Result<T, E> {
+        if let Some(x) = self {
+            Ok(x)
+        } else {
+            option_failure();
+            Err(err())
+        }
+    }
+
+    #[track_caller]
+    fn filter_log(self, predicate: impl FnOnce(&T) -> bool) -> Self
+    where
+        T: PayloadFormat,
+    {
+        let x = self?;
+        if predicate(&x) {
+            Some(x)
+        } else {
+            filter_error(&x);
+            None
+        }
+    }
+
+    #[track_caller]
+    fn map_or_log<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        if let Some(x) = self {
+            f(x)
+        } else {
+            option_failure();
+            default
+        }
+    }
+
+    #[track_caller]
+    fn map_or_else_log<U>(self, default: impl FnOnce() -> U, f: impl FnOnce(T) -> U) -> U {
+        if let Some(x) = self {
+            f(x)
+        } else {
+            option_failure();
+            default()
+        }
+    }
+
+    #[track_caller]
+    fn and_then_log<U>(self, f: impl FnOnce(T) -> Option<U>) -> Option<U> {
+        if let Some(x) = self {
+            f(x)
+        } else {
+            option_failure();
+            None
+        }
+    }
+
+    #[track_caller]
+    fn and_log<U>(self, other: Option<U>) -> Option<U> {
+        if self.is_some() {
+            other
+        } else {
+            option_failure();
+            None
+        }
+    }
+
+    #[track_caller]
+    fn log_or_break(self) -> ControlFlow<(), T> {
+        match self {
+            Some(x) => ControlFlow::Continue(x),
+            None => {
+                option_failure();
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    #[track_caller]
+    fn or_else_log(self, f: impl FnOnce() -> Option<T>) -> Option<T> {
+        if self.is_some() {
+            self
+        } else {
+            option_failure();
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn zip_log<U>(self, other: Option<U>) -> Option<(T, U)> {
+        match (self, other) {
+            (Some(x), Some(y)) => Some((x, y)),
+            (None, Some(_)) => {
+                none_in_error("the left of `zip`");
+                None
+            }
+            (Some(_), None) => {
+                none_in_error("the right of `zip`");
+                None
+            }
+            (None, None) => {
+                none_in_error("both sides of `zip`");
+                None
+            }
+        }
+    }
+
+    #[track_caller]
+    fn zip_with_log<U, R>(self, other: Option<U>, f: impl FnOnce(T, U) -> R) -> Option<R> {
+        match (self, other) {
+            (Some(x), Some(y)) => Some(f(x, y)),
+            (None, Some(_)) => {
+                none_in_error("the left of `zip_with`");
+                None
+            }
+            (Some(_), None) => {
+                none_in_error("the right of `zip_with`");
+                None
+            }
+            (None, None) => {
+                none_in_error("both sides of `zip_with`");
+                None
+            }
+        }
+    }
+
+    #[track_caller]
+    fn xor_log(self, other: Option<T>) -> Option<T> {
+        match (self, other) {
+            (Some(x), None) | (None, Some(x)) => Some(x),
+            (Some(_), Some(_)) => {
+                some_in_error("both sides of `xor`");
+                None
+            }
+            (None, None) => {
+                none_in_error("both sides of `xor`");
+                None
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_once(self, logged: &AtomicBool) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            gated_error(Gate::Once(logged), Encountered::None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_counted(self, count: &AtomicU32) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            gated_error(Gate::Counted(count), Encountered::None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_sampled(self, count: &AtomicU32, n: u32) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            gated_error(Gate::Sampled(count, n), Encountered::None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled(self, next: &AtomicU64, interval_ms: u64) -> T
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            gated_error(Gate::Throttled(next, interval_ms), Encountered::None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn __log_at_site(self, site: Site<'_>) -> Option<T> {
+        if self.is_none() {
+            site_error(site, Encountered::None);
+        }
+        self
+    }
+}
+
+impl<T> OptionMutExt for Option<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn get_or_insert_log(&mut self, value: T) -> &mut T {
+        if self.is_none() {
+            option_failure();
+        }
+        self.get_or_insert(value)
+    }
+
+    #[track_caller]
+    fn get_or_insert_with_log(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        if self.is_none() {
+            option_failure();
+        }
+        self.get_or_insert_with(f)
+    }
+
+    #[track_caller]
+    fn take_log(&mut self) -> Option<T> {
+        let taken = self.take();
+        if taken.is_none() {
+            took_none_error();
+        }
+        taken
+    }
+
+    #[track_caller]
+    fn replace_log(&mut self, value: T) -> Option<T> {
+        let replaced = self.replace(value);
+        if replaced.is_some() {
+            overwrote_some_error();
+        }
+        replaced
+    }
+}
+
+impl<T> OptionFlattenExt for Option<Option<T>> {
+    type Output = T;
+
+    #[track_caller]
+    fn flatten_log(self) -> Option<T> {
+        match self {
+            Some(Some(x)) => Some(x),
+            Some(None) => {
+                none_in_error("the inner `Option`");
+                None
+            }
+            None => {
+                none_in_error("the outer `Option`");
+                None
+            }
+        }
+    }
+}
+
+impl<T, E: PayloadFormat> OptionTransposeExt for Option<Result<T, E>> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn transpose_log(self) -> Result<Option<T>, E> {
+        match self {
+            Some(Ok(x)) => Ok(Some(x)),
+            Some(Err(err)) => {
+                result_failure(&err);
+                Err(err)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T, const N: usize> OptionArrayExt<T, N> for Option<[T; N]> {
+    #[track_caller]
+    fn unwrap_or_default_log_array(self) -> [T; N]
+    where
+        T: Default,
+    {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            core::array::from_fn(|_| T::default())
+        }
+    }
+}
+
+macro_rules! impl_option_non_zero_ext {
+    ($($t:ty),*) => {$(
+        impl OptionNonZeroExt for Option<$t> {
+            type Output = $t;
+
+            #[track_caller]
+            fn unwrap_or_one_log(self) -> $t {
+                const ONE: $t = match <$t>::new(1) {
+                    Some(one) => one,
+                    None => unreachable!(),
+                };
+                if let Some(x) = self {
+                    x
+                } else {
+                    option_failure();
+                    ONE
+                }
+            }
+        }
+    )*};
+}
+
+impl_option_non_zero_ext!(
+    core::num::NonZeroU8,
+    core::num::NonZeroU16,
+    core::num::NonZeroU32,
+    core::num::NonZeroU64,
+    core::num::NonZeroU128,
+    core::num::NonZeroUsize,
+    core::num::NonZeroI8,
+    core::num::NonZeroI16,
+    core::num::NonZeroI32,
+    core::num::NonZeroI64,
+    core::num::NonZeroI128,
+    core::num::NonZeroIsize
+);
+
+impl<T> OptionRefExt for Option<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn as_ref_or_log<'a>(&'a self, default: &'a T) -> &'a T {
+        if let Some(x) = self {
+            x
+        } else {
+            option_failure();
+            default
+        }
+    }
+}
+
+impl<T, E: PayloadFormat> ResultRefExt for Result<T, E> {
+    type Output = T;
+
+    #[track_caller]
+    fn as_ref_or_log<'a>(&'a self, default: &'a T) -> &'a T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(err);
+                default
+            }
+        }
+    }
+}
+
+impl<T: core::ops::Deref> OptionDerefExt for Option<T> {
+    type Target = T::Target;
+
+    #[track_caller]
+    fn as_deref_log(&self) -> Option<&T::Target> {
+        if self.is_none() {
+            option_failure();
+        }
+        self.as_deref()
+    }
+}
+
+impl<T, E: PayloadFormat> ResultExt for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_or_default_log(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_typed(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                let ty = (core::any::type_name::<T>(), core::any::type_name::<E>());
+                result_typed_error(None, None, ty, &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_flagged(self) -> (T, bool)
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => (x, false),
+            Err(err) => {
+                result_failure(&err);
+                (T::default(), true)
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn unwrap_or_default_log_captured(self) -> (T, Option<alloc::string::String>)
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => (x, None),
+            Err(err) => (T::default(), captured_error(Encountered::Err(&err))),
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_in(self, target: &'static str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error_in(target, &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    #[cfg(feature = "kv")]
+    fn unwrap_or_default_log_kv(self, key: &'static str, value: &str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error_kv((key, value), &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_logged(self) -> LoggedDefault<T>
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => LoggedDefault::real(x),
+            Err(err) => {
+                result_failure(&err);
+                LoggedDefault::defaulted(T::default())
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_metered(self) -> Logged<T>
+    where
+        T: Default,
+    {
+        Logged::new(self.unwrap_or_default_logged())
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_with(self, f: impl FnOnce(E) -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                f(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_ref(self, f: impl FnOnce(&E) -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                f(&err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log(self, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_into(self, default: impl Into<T>) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                default.into()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_const(self, default: &T) -> T
+    where
+        T: Copy,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_failure(&err);
+                *default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_verbose(self) -> T
+    where
+        T: Default + PayloadFormat,
+    {
+        match self {
+            Ok(x) => {
+                resolved(Level::Trace, Resolved::Ok(&x));
+                x
+            }
+            Err(err) => {
+                result_failure(&err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_verbose(self, f: impl FnOnce() -> T) -> T
+    where
+        T: PayloadFormat,
+    {
+        match self {
+            Ok(x) => {
+                resolved(Level::Trace, Resolved::Ok(&x));
+                x
+            }
+            Err(err) => {
+                result_failure(&err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_verbose(self, default: T) -> T
+    where
+        T: PayloadFormat,
+    {
+        match self {
+            Ok(x) => {
+                resolved(Level::Trace, Resolved::Ok(&x));
+                x
+            }
+            Err(err) => {
+                result_failure(&err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_at(self, level: impl Into<Level>) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(Some(level.into()), None, &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_leveled<L: Into<Level>>(self, level_of: impl FnOnce(&E) -> L) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(Some(level_of(&err).into()), None, &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_at(self, level: impl Into<Level>, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(Some(level.into()), None, &err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_at(self, level: impl Into<Level>, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(Some(level.into()), None, &err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_as<L: StaticLevel>(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                static_error::<L>(Encountered::Err(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_as<L: StaticLevel>(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                static_error::<L>(Encountered::Err(&err));
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_as<L: StaticLevel>(self, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                static_error::<L>(Encountered::Err(&err));
+                default
+            }
+        }
+    }
+
+    fn unwrap_or_default_log_at_location(self, location: &'static Location<'static>) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error_at(location, None, &err);
+                T::default()
+            }
+        }
+    }
+
+    fn unwrap_or_else_log_at_location(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error_at(location, None, &err);
+                f()
+            }
+        }
+    }
+
+    fn unwrap_or_log_at_location(self, location: &'static Location<'static>, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error_at(location, None, &err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_expect_log(self, msg: &str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(None, Some(msg), &err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_expect_log(self, msg: &str, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(None, Some(msg), &err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_expect_log(self, msg: &str, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                result_error(None, Some(msg), &err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn inspect_err_log(self) -> Self {
+        if let Err(err) = &self {
+            result_failure(err);
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_ok_log(self, level: impl Into<Level>) -> Self
+    where
+        T: PayloadFormat,
+    {
+        if let Ok(x) = &self {
+            resolved(level.into(), Resolved::Ok(x));
+        }
+        self
+    }
+
+    #[track_caller]
+    fn inspect_err_with(self, f: impl FnOnce(&E)) -> Self {
+        if let Err(err) = &self {
+            result_failure(err);
+            f(err);
+        }
+        self
+    }
+
+    #[track_caller]
+    fn ok_log(self) -> Option<T> {
+        match self {
+            Ok(x) => Some(x),
+            Err(err) => {
+                result_failure(&err);
+                None
+            }
+        }
+    }
+
+    #[track_caller]
+    fn map_err_log<F>(self, op: impl FnOnce(E) -> F) -> Result<T, F> {
+        match self {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                result_failure(&err);
+                Err(op(err))
+            }
+        }
+    }
+
+    #[track_caller]
+    fn map_or_log<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            Ok(x) => f(x),
+            Err(err) => {
+                result_failure(&err);
+                default
+            }
+        }
+    }
+
+    #[track_caller]
+    fn map_or_else_log<U>(self, default: impl FnOnce(E) -> U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            Ok(x) => f(x),
+            Err(err) => {
+                result_failure(&err);
+                default(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn and_then_log<U>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<U, E> {
+        match self {
+            Ok(x) => f(x),
+            Err(err) => {
+                result_failure(&err);
+                Err(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn and_log<U>(self, other: Result<U, E>) -> Result<U, E> {
+        match self {
+            Ok(_) => other,
+            Err(err) => {
+                result_failure(&err);
+                Err(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn log_or_break(self) -> ControlFlow<(), T> {
+        match self {
+            Ok(x) => ControlFlow::Continue(x),
+            Err(err) => {
+                result_failure(&err);
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    #[track_caller]
+    fn or_else_log<F>(self, f: impl FnOnce(E) -> Result<T, F>) -> Result<T, F> {
+        match self {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                result_failure(&err);
+                f(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn or_try_log<F: PayloadFormat>(self, f: impl FnOnce() -> Result<T, F>) -> Result<T, F> {
+        match self {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                result_failure(&err);
+                let ret = f();
+                if let Err(err) = &ret {
+                    result_failure(err);
+                }
+                ret
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_once(self, logged: &AtomicBool) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                gated_error(Gate::Once(logged), Encountered::Err(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_counted(self, count: &AtomicU32) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                gated_error(Gate::Counted(count), Encountered::Err(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_sampled(self, count: &AtomicU32, n: u32) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                gated_error(Gate::Sampled(count, n), Encountered::Err(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled(self, next: &AtomicU64, interval_ms: u64) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                gated_error(Gate::Throttled(next, interval_ms), Encountered::Err(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn __log_at_site(self, site: Site<'_>) -> Option<T> {
+        match self {
+            Ok(x) => Some(x),
+            Err(err) => {
+                site_error(site, Encountered::Err(&err));
+                None
+            }
+        }
+    }
+}
+
+impl<T, E> ResultExtNoDbg for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_or_default_log_no_dbg(self) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_failure();
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_flagged_no_dbg(self) -> (T, bool)
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            (x, false)
+        } else {
+            no_dbg_failure();
+            (T::default(), true)
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_no_dbg(self, f: impl FnOnce() -> T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_failure();
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_with_no_dbg(self, f: impl FnOnce(E) -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                no_dbg_failure();
+                f(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_ref_no_dbg(self, f: impl FnOnce(&E) -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                no_dbg_failure();
+                f(&err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_no_dbg(self, default: T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_failure();
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_into_no_dbg(self, default: impl Into<T>) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_failure();
+            default.into()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_at_no_dbg(self, level: impl Into<Level>) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(Some(level.into()), None);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_at_no_dbg(self, level: impl Into<Level>, f: impl FnOnce() -> T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(Some(level.into()), None);
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_at_no_dbg(self, level: impl Into<Level>, default: T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(Some(level.into()), None);
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_as_no_dbg<L: StaticLevel>(self) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::ErrNoDbg);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_as_no_dbg<L: StaticLevel>(self, f: impl FnOnce() -> T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::ErrNoDbg);
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_as_no_dbg<L: StaticLevel>(self, default: T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            static_error::<L>(Encountered::ErrNoDbg);
+            default
+        }
+    }
+
+    fn unwrap_or_default_log_at_location_no_dbg(self, location: &'static Location<'static>) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error_at(location, None);
+            T::default()
+        }
+    }
+
+    fn unwrap_or_else_log_at_location_no_dbg(
+        self,
+        location: &'static Location<'static>,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error_at(location, None);
+            f()
+        }
+    }
+
+    fn unwrap_or_log_at_location_no_dbg(
+        self,
+        location: &'static Location<'static>,
+        default: T,
+    ) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error_at(location, None);
+            default
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_expect_log_no_dbg(self, msg: &str) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(None, Some(msg));
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_expect_log_no_dbg(self, msg: &str, f: impl FnOnce() -> T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(None, Some(msg));
+            f()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_expect_log_no_dbg(self, msg: &str, default: T) -> T {
+        if let Ok(x) = self {
+            x
+        } else {
+            no_dbg_error(None, Some(msg));
+            default
+        }
+    }
+
+    #[track_caller]
+    fn inspect_err_log_no_dbg(self) -> Self {
+        if self.is_err() {
+            no_dbg_failure();
+        }
+        self
+    }
+
+    #[track_caller]
+    fn ok_log_no_dbg(self) -> Option<T> {
+        if let Ok(x) = self {
+            Some(x)
+        } else {
+            no_dbg_failure();
+            None
+        }
+    }
+
+    #[track_caller]
+    fn map_or_log_no_dbg<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        if let Ok(x) = self {
+            f(x)
+        } else {
+            no_dbg_failure();
+            default
+        }
+    }
+
+    #[track_caller]
+    fn map_or_else_log_no_dbg<U>(self, default: impl FnOnce(E) -> U, f: impl FnOnce(T) -> U) -> U {
+        match self {
+            Ok(x) => f(x),
+            Err(err) => {
+                no_dbg_failure();
+                default(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn and_then_log_no_dbg<U>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<U, E> {
+        match self {
+            Ok(x) => f(x),
+            Err(err) => {
+                no_dbg_failure();
+                Err(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn and_log_no_dbg<U>(self, other: Result<U, E>) -> Result<U, E> {
+        match self {
+            Ok(_) => other,
+            Err(err) => {
+                no_dbg_failure();
+                Err(err)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_once_no_dbg(self, logged: &AtomicBool) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            gated_error(Gate::Once(logged), Encountered::ErrNoDbg);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_default_log_sampled_no_dbg(self, count: &AtomicU32, n: u32) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            gated_error(Gate::Sampled(count, n), Encountered::ErrNoDbg);
+            T::default()
+        }
+    }
+
+    #[track_caller]
+    #[cfg(target_has_atomic = "64")]
+    fn unwrap_or_default_log_throttled_no_dbg(self, next: &AtomicU64, interval_ms: u64) -> T
+    where
+        T: Default,
+    {
+        if let Ok(x) = self {
+            x
+        } else {
+            gated_error(Gate::Throttled(next, interval_ms), Encountered::ErrNoDbg);
+            T::default()
+        }
+    }
+}
+
+/// Like `unwrap_or_default_log`, but includes the unwrapped expression in the message.
+///
+/// ```rust
+/// use unwrap_log::unwrap_or_default_log;
+///
+/// let port: u16 = unwrap_or_default_log!("http".parse());
+/// assert_eq!(port, 0);
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log {
+    ($e:expr $(,)?) => {{
+        let value = $e;
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!()).msg(::core::concat!(
+                "`",
+                ::core::stringify!($e),
+                "`"
+            ));
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+/// Like `unwrap_or_else_log`, but includes the unwrapped expression in the message.
+#[macro_export]
+macro_rules! unwrap_or_else_log {
+    ($e:expr, $f:expr $(,)?) => {{
+        let (value, f) = ($e, $f);
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!()).msg(::core::concat!(
+                "`",
+                ::core::stringify!($e),
+                "`"
+            ));
+            value.__log_at_site(site).unwrap_or_else(f)
+        }
+    }};
+}
+
+/// Like `unwrap_or_log`, but includes the unwrapped expression in the message.
+#[macro_export]
+macro_rules! unwrap_or_log {
+    ($e:expr, $default:expr $(,)?) => {{
+        let (value, default) = ($e, $default);
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!()).msg(::core::concat!(
+                "`",
+                ::core::stringify!($e),
+                "`"
+            ));
+            value.__log_at_site(site).unwrap_or(default)
+        }
+    }};
+}
+
+/// Like `unwrap_or_default_log`, but logs only the first failure at this call site.
+///
+/// Each expansion of this macro declares its own `static` flag, so "once" means once per call
+/// site for the lifetime of the process, regardless of thread or of the value unwrapped.
+///
+/// ```rust
+/// use unwrap_log::unwrap_or_default_log_once;
+///
+/// for _ in 0..1000 {
+///     let x: i32 = unwrap_or_default_log_once!(None);
+///     assert_eq!(x, 0);
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log_once {
+    ($e:expr $(,)?) => {{
+        static LOGGED: ::core::sync::atomic::AtomicBool =
+            ::core::sync::atomic::AtomicBool::new(false);
+        let value = $e;
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!())
+                .gate($crate::__private::Gate::Once(&LOGGED));
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+/// Like `unwrap_or_default_log`, but emits `log` records with the given target, such as one
+/// derived from `module_path!()`.
+///
+/// This takes precedence over the [global target](set_target), but only for this call site.
+///
+/// ```rust
+/// use unwrap_log::unwrap_or_default_log_in;
+///
+/// let x: i32 = unwrap_or_default_log_in!(concat!(module_path!(), "::unwrap"), None);
+/// assert_eq!(x, 0);
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log_in {
+    ($target:expr, $e:expr $(,)?) => {{
+        let value = $e;
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!()).target($target);
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+/// Like `unwrap_or_default_log`, but includes the number of failures at this call site in the
+/// message, and as an `occurrence` field with `kv` or `tracing`.
+///
+/// Each expansion of this macro declares its own `static` tally.
+///
+/// ```rust
+/// use unwrap_log::unwrap_or_default_log_counted;
+///
+/// for _ in 0..3 {
+///     // Logs "... encountered `None` (x1)", then (x2), then (x3).
+///     let x: i32 = unwrap_or_default_log_counted!(None);
+///     assert_eq!(x, 0);
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log_counted {
+    ($e:expr $(,)?) => {{
+        static COUNT: ::core::sync::atomic::AtomicU32 = ::core::sync::atomic::AtomicU32::new(0);
+        let value = $e;
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!())
+                .gate($crate::__private::Gate::Counted(&COUNT));
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+/// Unwraps an `Option` or `Result`, or logs at the [default level](default_level) and returns
+/// early from the enclosing function.
+///
+/// Like `?`, but rather than propagating the failure, this returns `Default::default()`, or the
+/// given value if there is a second argument.
+///
+/// ```rust
+/// use unwrap_log::log_try;
+///
+/// fn total(a: &str, b: &str) -> u32 {
+///     let a: u32 = log_try!(a.parse());
+///     let b: u32 = log_try!(b.parse(), u32::MAX);
+///     a + b
+/// }
+///
+/// assert_eq!(total("1", "2"), 3);
+/// assert_eq!(total("x", "2"), 0);
+/// assert_eq!(total("1", "y"), u32::MAX);
+/// ```
+#[macro_export]
+macro_rules! log_try {
+    ($e:expr $(,)?) => {
+        $crate::log_try!($e, ::core::default::Default::default())
+    };
+    ($e:expr, $default:expr $(,)?) => {{
+        let value = $e;
+        #[allow(unused_imports)]
+        use $crate::{OptionExt as _, ResultExt as _};
+        match value.__log_at_site($crate::__private::Site::new(::core::module_path!())) {
+            ::core::option::Option::Some(x) => x,
+            ::core::option::Option::None => return $default,
+        }
+    }};
+}
+
+impl<T, E: core::fmt::Display> ResultExtDisplay for Result<T, E> {
+    type Output = T;
+
+    #[track_caller]
+    fn unwrap_or_default_log_display(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_display(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_display(self, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&err);
+                default
+            }
+        }
+    }
+}
+
+impl<T, E> ResultExtRedact for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_or_default_log_redacted(self, redact: impl FnOnce(&E) -> &str) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                redacted_error(redact(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_redacted(
+        self,
+        redact: impl FnOnce(&E) -> &str,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                redacted_error(redact(&err));
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_redacted(self, redact: impl FnOnce(&E) -> &str, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                redacted_error(redact(&err));
+                default
+            }
+        }
+    }
+}
+
+impl<T, E> ResultExtCode for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_or_default_log_code(self, code: impl FnOnce(&E) -> u32) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                code_error(code(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_code(self, code: impl FnOnce(&E) -> u32, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                code_error(code(&err));
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_code(self, code: impl FnOnce(&E) -> u32, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                code_error(code(&err));
+                default
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E: std::error::Error> ResultExtSource for Result<T, E> {
+    type Output = T;
+
+    #[track_caller]
+    fn unwrap_or_default_log_source(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                source_error(&err);
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_source(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                source_error(&err);
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_source(self, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                source_error(&err);
+                default
+            }
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl<T> ResultExtAnyhow for Result<T, anyhow::Error> {
+    type Output = T;
+
+    #[track_caller]
+    fn unwrap_or_default_log_anyhow(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&Alternate(&err));
+                T::default()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_else_log_anyhow(self, f: impl FnOnce() -> T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&Alternate(&err));
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_or_log_anyhow(self, default: T) -> T {
+        match self {
+            Ok(x) => x,
+            Err(err) => {
+                display_error(&Alternate(&err));
+                default
+            }
+        }
+    }
+}
+
+impl<T: PayloadFormat, E> ResultErrExt for Result<T, E> {
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_err_or_default_log(self) -> E
+    where
+        E: Default,
+    {
+        match self {
+            Ok(x) => {
+                ok_failure(&x);
+                E::default()
+            }
+            Err(err) => err,
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_err_or_else_log(self, f: impl FnOnce() -> E) -> E {
+        match self {
+            Ok(x) => {
+                ok_failure(&x);
+                f()
+            }
+            Err(err) => err,
+        }
+    }
+
+    #[track_caller]
+    fn unwrap_err_or_log(self, default: E) -> E {
+        match self {
+            Ok(x) => {
+                ok_failure(&x);
+                default
+            }
+            Err(err) => err,
+        }
+    }
+
+    #[track_caller]
+    fn expect_err_or_default_log(self, msg: &str) -> E
+    where
+        E: Default,
+    {
+        match self {
+            Ok(x) => {
+                ok_error(None, Some(msg), &x);
+                E::default()
+            }
+            Err(err) => err,
+        }
+    }
+}
+
+impl<T: Default> LogDefault for Option<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn into_or_default_log(self) -> T {
+        self.unwrap_or_default_log()
+    }
+}
+
+impl<T: Default, E: PayloadFormat> LogDefault for Result<T, E> {
+    type Output = T;
+
+    #[track_caller]
+    fn into_or_default_log(self) -> T {
+        ResultExt::unwrap_or_default_log(self)
+    }
+}
+
+impl<T> TryIntoExt for T {
+    #[track_caller]
+    fn try_into_or_default_log<U>(self) -> U
+    where
+        U: TryFrom<T> + Default,
+        U::Error: PayloadFormat,
+    {
+        ResultExt::unwrap_or_default_log(U::try_from(self))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> OptionExtAsync for Option<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn unwrap_or_else_log_async<Fut: core::future::Future<Output = T>>(
+        self,
+        f: impl FnOnce() -> Fut,
+    ) -> impl core::future::Future<Output = T> {
+        let fallback = if let Some(x) = self {
+            Ok(x)
+        } else {
+            option_failure();
+            Err(f)
+        };
+        async move {
+            match fallback {
+                Ok(x) => x,
+                Err(f) => f().await,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, E: PayloadFormat> ResultExtAsync for Result<T, E> {
+    type Output = T;
+    type Error = E;
+
+    #[track_caller]
+    fn unwrap_or_else_log_async<Fut: core::future::Future<Output = T>>(
+        self,
+        f: impl FnOnce(E) -> Fut,
+    ) -> impl core::future::Future<Output = T> {
+        if let Err(err) = &self {
+            result_failure(err);
+        }
+        async move {
+            match self {
+                Ok(x) => x,
+                Err(err) => f(err).await,
+            }
+        }
+    }
+}
+
+/// Like `unwrap_or_default_log`, but logs only every `n`th failure at this call site.
+///
+/// Each expansion of this macro declares its own `static` tally, so sampling is per call site,
+/// shared by all threads, rather than global. With a nonzero `n`, the first failure is always
+/// logged.
+///
+/// ```rust
+/// use unwrap_log::unwrap_or_default_log_sampled;
+///
+/// for _ in 0..1000 {
+///     let x: i32 = unwrap_or_default_log_sampled!(None, 100);
+///     assert_eq!(x, 0);
+/// }
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_default_log_sampled {
+    ($e:expr, $n:expr $(,)?) => {{
+        static COUNT: ::core::sync::atomic::AtomicU32 = ::core::sync::atomic::AtomicU32::new(0);
+        let (value, n) = ($e, $n);
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!())
+                .gate($crate::__private::Gate::Sampled(&COUNT, n));
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+/// Like `unwrap_or_default_log`, but logs at most once every `interval_ms` milliseconds at this
+/// call site, according to the [installed clock](set_clock).
+///
+/// Each expansion of this macro declares its own `static` timestamp, so throttling is per call
+/// site, shared by all threads. Without a clock, every failure is logged.
+///
+/// ```rust
+/// use unwrap_log::{unwrap_or_default_log_throttled, Clock};
+///
+/// struct Frozen;
+///
+/// impl Clock for Frozen {
+///     fn now_millis(&self) -> u64 {
+///         0
+///     }
+/// }
+///
+/// unwrap_log::set_clock(&Frozen);
+/// for _ in 0..1000 {
+///     let x: i32 = unwrap_or_default_log_throttled!(None, 5000);
+///     assert_eq!(x, 0);
+/// }
+/// ```
+#[cfg(target_has_atomic = "64")]
+#[macro_export]
+macro_rules! unwrap_or_default_log_throttled {
+    ($e:expr, $interval_ms:expr $(,)?) => {{
+        static NEXT: ::core::sync::atomic::AtomicU64 = ::core::sync::atomic::AtomicU64::new(0);
+        let (value, interval_ms) = ($e, $interval_ms);
+        {
+            #[allow(unused_imports)]
+            use $crate::{OptionExt as _, ResultExt as _};
+            let site = $crate::__private::Site::new(::core::module_path!())
+                .gate($crate::__private::Gate::Throttled(&NEXT, interval_ms));
+            value.__log_at_site(site).unwrap_or_default()
+        }
+    }};
+}
+
+fn sampled(count: &AtomicU32, n: u32) -> bool {
+    count.fetch_add(1, Ordering::Relaxed).checked_rem(n) == Some(0)
+}
+
+#[cfg(target_has_atomic = "64")]
+fn throttled(next: &AtomicU64, interval_ms: u64) -> bool {
+    let Some(clock) = clock::clock() else {
+        return true;
+    };
+    let now = clock.now_millis();
+    let allowed = next.load(Ordering::Relaxed);
+    now >= allowed
+        && next
+            .compare_exchange(
+                allowed,
+                now.saturating_add(interval_ms),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+}
+
+/// Reports a failure from `site`, if it passes the gate, or else only counts it.
+///
+/// Keeping the gate out of line leaves the rate-limited methods as thin generic shims.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn site_error(site: Site<'_>, encountered: Encountered<'_>) {
+    let caller = Location::caller();
+    let occurrence = match site.gate {
+        Some(Gate::Counted(count)) => {
+            Some(count.fetch_add(1, Ordering::Relaxed).wrapping_add(1).into())
+        }
+        Some(gate) if !gate.allows() => {
+            record_failure(caller, encountered);
+            return;
+        }
+        _ => None,
+    };
+    let extra = Extra {
+        occurrence,
+        target: site.target,
+        #[cfg(any(feature = "log", feature = "alloc"))]
+        module: site.module,
+        ..Extra::default()
+    };
+    report_with(caller, None, site.msg, encountered, extra);
+}
+
+/// Like `site_error`, for a method call gated by `gate`.
+#[inline]
+#[track_caller]
+fn gated_error(gate: Gate<'_>, encountered: Encountered<'_>) {
+    let site = Site::UNKNOWN;
+    site_error(site.gate(gate), encountered);
+}
+
+/// Reports a failure at the level of `L`, or only counts it if `log` compiles that level out.
+///
+/// The check is a constant, so a call site at a compiled-out level keeps no reporting code.
+#[inline(always)]
+#[track_caller]
+fn static_error<L: StaticLevel>(encountered: Encountered<'_>) {
+    if const { level::compiled_out(L::LEVEL) } {
+        count_only(encountered.kind());
+    } else {
+        leveled_error(L::LEVEL, encountered);
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn leveled_error(level: Level, encountered: Encountered<'_>) {
+    report(Location::caller(), Some(level), None, encountered);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn count_only(kind: FailureKind) {
+    count_failure(Location::caller(), kind);
+}
+
+/// Like `option_error` at the default level and without a message, as most methods report, so
+/// that their call sites pass only the location.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn option_failure() {
+    report(Location::caller(), None, None, Encountered::None);
+}
+
+/// Like `result_error` at the default level and without a message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn result_failure(err: &DynPayload<'_>) {
+    report(Location::caller(), None, None, Encountered::Err(err));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn option_error(level: Option<Level>, msg: Option<&str>) {
+    report(Location::caller(), level, msg, Encountered::None);
+}
+
+#[cold]
+#[inline(never)]
+fn option_error_at(location: &'static Location<'static>, level: Option<Level>) {
+    report(location, level, None, Encountered::None);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[cfg(feature = "kv")]
+fn option_error_kv(context: (&'static str, &str)) {
+    report_with(
+        Location::caller(),
+        None,
+        None,
+        Encountered::None,
+        Extra {
+            context: Some(context),
+            ..Extra::default()
+        },
+    );
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn option_error_in(target: &'static str) {
+    let extra = Extra {
+        target: Some(target),
+        ..Extra::default()
+    };
+    report_with(Location::caller(), None, None, Encountered::None, extra);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn option_typed_error(ty: &'static str) {
+    report(Location::caller(), None, None, Encountered::NoneOf(ty));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn result_error(level: Option<Level>, msg: Option<&str>, err: &DynPayload<'_>) {
+    report(Location::caller(), level, msg, Encountered::Err(err));
+}
+
+#[cold]
+#[inline(never)]
+fn result_error_at(
+    location: &'static Location<'static>,
+    level: Option<Level>,
+    err: &DynPayload<'_>,
+) {
+    report(location, level, None, Encountered::Err(err));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[cfg(feature = "kv")]
+fn result_error_kv(context: (&'static str, &str), err: &DynPayload<'_>) {
+    report_with(
+        Location::caller(),
+        None,
+        None,
+        Encountered::Err(err),
+        Extra {
+            context: Some(context),
+            ..Extra::default()
+        },
+    );
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn result_error_in(target: &'static str, err: &DynPayload<'_>) {
+    let extra = Extra {
+        target: Some(target),
+        ..Extra::default()
+    };
+    report_with(Location::caller(), None, None, Encountered::Err(err), extra);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn result_typed_error(
+    level: Option<Level>,
+    msg: Option<&str>,
+    ty: (&'static str, &'static str),
+    err: &DynPayload<'_>,
+) {
+    report(Location::caller(), level, msg, Encountered::ErrOf(ty, err));
+}
+
+/// Like `ok_error` at the default level and without a message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn ok_failure(x: &DynPayload<'_>) {
+    report(Location::caller(), None, None, Encountered::Ok(x));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn ok_error(level: Option<Level>, msg: Option<&str>, x: &DynPayload<'_>) {
+    report(Location::caller(), level, msg, Encountered::Ok(x));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn display_error(err: &dyn core::fmt::Display) {
+    report(Location::caller(), None, None, Encountered::ErrDisplay(err));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn redacted_error(token: &str) {
+    report(
+        Location::caller(),
+        None,
+        None,
+        Encountered::ErrRedacted(token),
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn captured_error(encountered: Encountered<'_>) -> Option<alloc::string::String> {
+    let caller = Location::caller();
+    let message = report_with(caller, None, None, encountered, Extra::default())?;
+    Some(alloc::format!("{} {message}", Loc(caller)))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn code_error(code: u32) {
+    report(Location::caller(), None, None, Encountered::ErrCode(code));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[cfg(feature = "std")]
+fn source_error(err: &dyn std::error::Error) {
+    report(Location::caller(), None, None, Encountered::ErrChain(err));
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn took_none_error() {
+    report(Location::caller(), None, None, Encountered::TookNone);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn overwrote_some_error() {
+    report(Location::caller(), None, None, Encountered::OverwroteSome);
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn none_in_error(place: &'static str) {
+    report(Location::caller(), None, None, Encountered::NoneIn(place));
 }
 
-/// Extension trait providing tracing alternatives to `Result` unwrap methods.
-pub trait ResultExt {
-    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
-    type Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and returns a default value.
-    fn unwrap_or_default_log(self) -> Self::Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and computes a default value from a closure.
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and returns the provided default.
-    fn unwrap_or_log(self, default: Self::Output) -> Self::Output;
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn some_in_error(place: &'static str) {
+    report(Location::caller(), None, None, Encountered::SomeIn(place));
 }
 
-/// Like `ResultExt` for `Result<T, E>`, but doesn't require `E: Debug`.
-///
-/// This is provided for users who want to avoid logging sensitive information,
-/// or who want to slim down their log files.
-pub trait ResultExtNoDbg {
-    /// The type of the "successful" output, intended to be `T` for a `Result<T, E>`.
-    type Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and returns a default value.
-    fn unwrap_or_default_log(self) -> Self::Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and computes a default value from a closure.
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> Self::Output) -> Self::Output;
-    /// Returns the contained `Ok` value, or logs at the warn level and returns the provided default.
-    fn unwrap_or_log(self, default: Self::Output) -> Self::Output;
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn filter_error(x: &DynPayload<'_>) {
+    report(Location::caller(), None, None, Encountered::FilteredOut(x));
 }
 
-impl<T: Default> OptionExt for Option<T> {
-    type Output = T;
+/// Like `no_dbg_error` at the default level and without a message.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn no_dbg_failure() {
+    report(Location::caller(), None, None, Encountered::ErrNoDbg);
+}
 
-    #[track_caller]
-    fn unwrap_or_default_log(self) -> T {
-        if let Some(x) = self {
-            x
-        } else {
-            option_error();
-            T::default()
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn no_dbg_error(level: Option<Level>, msg: Option<&str>) {
+    report(Location::caller(), level, msg, Encountered::ErrNoDbg);
+}
+
+#[cold]
+#[inline(never)]
+fn no_dbg_error_at(location: &'static Location<'static>, level: Option<Level>) {
+    report(location, level, None, Encountered::ErrNoDbg);
+}
+
+fn report(
+    caller: &'static Location<'static>,
+    level: Option<Level>,
+    msg: Option<&str>,
+    encountered: Encountered,
+) {
+    report_with(caller, level, msg, encountered, Extra::default());
+}
+
+/// Per-call additions to a failure report, beyond those of `report`.
+#[derive(Clone, Copy, Default)]
+struct Extra<'a> {
+    /// A key-value pair which the `kv` backend attaches as a field.
+    context: Option<(&'static str, &'a str)>,
+    /// A tally of failures at this call site, which bypasses deduplication.
+    occurrence: Option<u64>,
+    /// A target for `log` records, which takes precedence over [`set_target`].
+    target: Option<&'static str>,
+    /// The module of the call site, if a macro captured it.
+    #[cfg(any(feature = "log", feature = "alloc"))]
+    module: Option<&'static str>,
+}
+
+/// Like `report`, but with `extra`, and returns the message if it was passed to a sink or
+/// backend, rather than a hook or nothing at all.
+#[cfg_attr(not(feature = "kv"), allow(unused_variables))]
+fn report_with<'a>(
+    caller: &'static Location<'static>,
+    level: Option<Level>,
+    msg: Option<&'a str>,
+    encountered: Encountered<'a>,
+    extra: Extra<'a>,
+) -> Option<Message<'a>> {
+    record_failure(caller, encountered);
+    if mute::muted() {
+        return None;
+    }
+    let Extra {
+        context,
+        occurrence,
+        target,
+        #[cfg(any(feature = "log", feature = "alloc"))]
+        module,
+    } = extra;
+    #[cfg(feature = "alloc")]
+    let occurrence = match occurrence {
+        Some(n) => Some(n),
+        None => match dedup::occurrence(caller) {
+            Some(n) if !n.is_power_of_two() => return None,
+            n => n,
+        },
+    };
+    #[cfg(all(feature = "seq", target_has_atomic = "64"))]
+    let sequence = Some(SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1);
+    #[cfg(not(all(feature = "seq", target_has_atomic = "64")))]
+    let sequence = None;
+    let message = Message {
+        msg,
+        encountered,
+        occurrence,
+        sequence,
+        #[cfg(feature = "kv")]
+        context,
+        #[cfg(feature = "log")]
+        target,
+        #[cfg(feature = "log")]
+        module,
+    };
+    if let Some(hook) = hook() {
+        hook(caller, encountered.kind());
+        return None;
+    }
+    #[cfg(feature = "alloc")]
+    let level = level.or_else(|| levels::level_at(module));
+    if level.is_none() && DEFAULT_OFF.load(Ordering::Relaxed) {
+        // The default level was turned off by `init_from_env`.
+        return None;
+    }
+    if let Some(sink) = sink::sink() {
+        let level = level.unwrap_or_else(default_level);
+        sink.report(level, caller, format_args!("{message}"));
+    } else {
+        emit(level, caller, &message);
+    }
+    Some(message)
+}
+
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing", feature = "defmt")),
+    allow(unused_variables)
+)]
+fn emit(level: Option<Level>, caller: &Location<'_>, message: &Message) {
+    let caller = Loc(caller);
+    #[cfg(any(feature = "log", feature = "tracing", feature = "defmt"))]
+    let level = level.unwrap_or_else(default_level);
+    #[cfg(feature = "fallback")]
+    if log::max_level() == log::LevelFilter::Off {
+        if let Some(fallback) = FALLBACK.get() {
+            use core::fmt::Write;
+            let mut line = Line::new();
+            let _ = write!(line, "{caller} {message}");
+            fallback(line.as_str());
+        }
+    }
+    #[cfg(feature = "log")]
+    let target = Target {
+        explicit: message.target,
+        module: message.module,
+    };
+    #[cfg(all(feature = "log", not(feature = "kv")))]
+    LogSink.log_to(target, level, caller.0, format_args!("{message}"));
+    #[cfg(feature = "kv")]
+    if level.within_log_max() {
+        use log::kv::Value;
+        let location = Some(("location", Value::from_dyn_display(&caller)));
+        let file = Some(("file", Value::from(caller.file())));
+        let line = Some(("line", Value::from(caller.0.line())));
+        let column = Some(("column", Value::from(caller.0.column())));
+        let context = message
+            .context
+            .map(|(key, value)| (key, Value::from(value)));
+        let occurrence = message.occurrence.map(|n| ("occurrence", Value::from(n)));
+        let err = match message.encountered {
+            #[cfg(not(feature = "defmt"))]
+            Encountered::Err(err) => Some((Value::from_dyn_debug(err), None)),
+            #[cfg(not(feature = "defmt"))]
+            Encountered::ErrOf(types, err) => Some((Value::from_dyn_debug(err), Some(types))),
+            _ => None,
+        };
+        match err {
+            Some((err, types)) => log_record(
+                target,
+                level,
+                caller,
+                &[
+                    location,
+                    file,
+                    line,
+                    column,
+                    Some(("err", err)),
+                    context,
+                    occurrence,
+                ],
+                format_args!(
+                    "{caller} {}{}{}encountered `Err{}`{}",
+                    Sequence(message.sequence),
+                    Prefix,
+                    Msg(message.msg),
+                    ErrTypes(types),
+                    Occurrence(message.occurrence)
+                ),
+            ),
+            None => log_record(
+                target,
+                level,
+                caller,
+                &[location, file, line, column, context, occurrence],
+                format_args!("{caller} {message}"),
+            ),
+        }
+    }
+    #[cfg(feature = "log")]
+    if level != Level::Trace && TRACE_DETAILS.load(Ordering::Relaxed) {
+        trace_details(caller, target, message);
+    }
+    #[cfg(feature = "tracing")]
+    {
+        macro_rules! event {
+            (@ $level:ident, $($field:tt)*) => {
+                tracing::event!(
+                    tracing::Level::$level,
+                    location = %caller,
+                    defaulted = true,
+                    occurrence = message.occurrence,
+                    $($field)*
+                    "{message}"
+                )
+            };
+            ($($field:tt)*) => {
+                match level {
+                    Level::Error => event!(@ ERROR, $($field)*),
+                    Level::Warn => event!(@ WARN, $($field)*),
+                    Level::Info => event!(@ INFO, $($field)*),
+                    Level::Debug => event!(@ DEBUG, $($field)*),
+                    Level::Trace => event!(@ TRACE, $($field)*),
+                }
+            };
+        }
+        match message.encountered {
+            #[cfg(not(feature = "defmt"))]
+            Encountered::Err(err) | Encountered::ErrOf(_, err) => event!(error = ?err,),
+            Encountered::ErrDisplay(err) => event!(error = %err,),
+            #[cfg(feature = "std")]
+            Encountered::ErrChain(err) => event!(error = %err,),
+            _ => event!(),
+        }
+    }
+    #[cfg(feature = "defmt")]
+    {
+        let (file, line, column) = (caller.file(), caller.0.line(), caller.0.column());
+        let prefix = message_prefix().unwrap_or("");
+        let (msg, sep) = message.msg.map_or(("", ""), |msg| (msg, ": "));
+        macro_rules! event {
+            ($level:ident, $($arg:tt)*) => {
+                match level {
+                    Level::Error => defmt::error!($($arg)*),
+                    Level::Warn => defmt::warn!($($arg)*),
+                    Level::Info => defmt::info!($($arg)*),
+                    Level::Debug => defmt::debug!($($arg)*),
+                    Level::Trace => defmt::trace!($($arg)*),
+                }
+            };
+        }
+        match message.encountered {
+            Encountered::None => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `None`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep
+            ),
+            Encountered::NoneOf(ty) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `None::<{=str}>`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                ty
+            ),
+            Encountered::Err(err) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err({})`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                DynFormat(err)
+            ),
+            Encountered::ErrOf((t, e), err) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err::<{=str}, {=str}>({})`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                t,
+                e,
+                DynFormat(err)
+            ),
+            Encountered::ErrDisplay(err) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err`: {}",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                defmt::Display2Format(err)
+            ),
+            #[cfg(feature = "std")]
+            Encountered::ErrChain(_) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err(..)`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep
+            ),
+            Encountered::ErrNoDbg => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err(_)`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep
+            ),
+            Encountered::ErrRedacted(token) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err(_)`: {=str}",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                token
+            ),
+            Encountered::ErrCode(code) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Err[kind={=u32}]`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                code
+            ),
+            Encountered::Ok(x) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Ok({})`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                DynFormat(x)
+            ),
+            Encountered::FilteredOut(x) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}filtered out `Some({})`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                DynFormat(x)
+            ),
+            Encountered::TookNone => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}took from `None`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep
+            ),
+            Encountered::OverwroteSome => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}overwrote `Some`",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep
+            ),
+            Encountered::NoneIn(place) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `None` in {=str}",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                place
+            ),
+            Encountered::SomeIn(place) => event!(
+                level,
+                "{=str}:{=u32}:{=u32} {=str}{=str}{=str}encountered `Some` in {=str}",
+                file,
+                line,
+                column,
+                prefix,
+                msg,
+                sep,
+                place
+            ),
         }
     }
+}
 
-    #[track_caller]
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> T) -> T {
-        if let Some(x) = self {
-            x
-        } else {
-            option_error();
-            f()
+/// Logs `message` again at `trace`, with details too costly for the terse record.
+#[cfg(feature = "log")]
+#[cold]
+#[inline(never)]
+fn trace_details(caller: Loc<'_>, target: Target<'_>, message: &Message) {
+    if !LogSink.enabled(target, Level::Trace) {
+        return;
+    }
+    let details = Details {
+        kind: message.encountered.kind(),
+        #[cfg(feature = "std")]
+        backtrace: std::backtrace::Backtrace::force_capture(),
+    };
+    LogSink.log_to(
+        target,
+        Level::Trace,
+        caller.0,
+        format_args!("{message} {details}"),
+    );
+}
+
+/// Displays the details of a failure for [`set_trace_details`].
+#[cfg(feature = "log")]
+struct Details {
+    kind: FailureKind,
+    #[cfg(feature = "std")]
+    backtrace: std::backtrace::Backtrace,
+}
+
+#[cfg(feature = "log")]
+impl core::fmt::Display for Details {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[kind: {:?}", self.kind)?;
+        #[cfg(feature = "std")]
+        {
+            let thread = std::thread::current();
+            write!(f, ", thread: {}]", thread.name().unwrap_or("<unnamed>"))?;
+            write!(f, "\n{}", self.backtrace)
         }
+        #[cfg(not(feature = "std"))]
+        f.write_str("]")
     }
+}
 
-    #[track_caller]
-    fn unwrap_or_log(self, default: T) -> T {
-        if let Some(x) = self {
-            x
-        } else {
-            option_error();
-            default
+/// Emits a `log` record with structured fields, attributed to the file and line of `caller`.
+#[cfg(feature = "kv")]
+fn log_record(
+    target: Target<'_>,
+    level: Level,
+    caller: Loc<'_>,
+    fields: &[Option<(&str, log::kv::Value<'_>)>],
+    args: core::fmt::Arguments<'_>,
+) {
+    log::logger().log(
+        &log::Record::builder()
+            .args(args)
+            .level(level.into())
+            .target(target.name())
+            .module_path(target.module)
+            .file(Some(caller.file()))
+            .line(Some(caller.0.line()))
+            .key_values(&fields)
+            .build(),
+    );
+}
+
+/// Logs the summary of a [`FailureScope`], bypassing the hook and failure count.
+#[cfg(target_has_atomic = "64")]
+pub(crate) fn summarize(caller: &'static Location<'static>, name: &str, count: u64) {
+    if DEFAULT_OFF.load(Ordering::Relaxed) || mute::muted() {
+        return;
+    }
+    let level = default_level();
+    let last = LAST_FAILURE.get().unwrap_or(caller);
+    let summary = Summary { name, count, last };
+    if let Some(sink) = sink::sink() {
+        sink.report(level, caller, format_args!("{summary}"));
+    } else {
+        emit_summary(level, caller, &summary);
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing", feature = "defmt")),
+    allow(unused_variables)
+)]
+fn emit_summary(level: Level, caller: &Location<'_>, summary: &Summary) {
+    #[cfg(feature = "log")]
+    {
+        LogSink.log_to(Target::default(), level, caller, format_args!("{summary}"));
+    }
+    #[cfg(feature = "tracing")]
+    {
+        let caller = Loc(caller);
+        match level {
+            Level::Error => tracing::error!(location = %caller, "{summary}"),
+            Level::Warn => tracing::warn!(location = %caller, "{summary}"),
+            Level::Info => tracing::info!(location = %caller, "{summary}"),
+            Level::Debug => tracing::debug!(location = %caller, "{summary}"),
+            Level::Trace => tracing::trace!(location = %caller, "{summary}"),
+        }
+    }
+    #[cfg(feature = "defmt")]
+    {
+        let (caller, last) = (Loc(caller), Loc(summary.last));
+        macro_rules! event {
+            ($($arg:tt)*) => {
+                match level {
+                    Level::Error => defmt::error!($($arg)*),
+                    Level::Warn => defmt::warn!($($arg)*),
+                    Level::Info => defmt::info!($($arg)*),
+                    Level::Debug => defmt::debug!($($arg)*),
+                    Level::Trace => defmt::trace!($($arg)*),
+                }
+            };
         }
+        event!(
+            "{=str}:{=u32}:{=u32} {=u64} failed unwraps in {=str}, most recently at {=str}:{=u32}:{=u32}",
+            caller.file(),
+            caller.0.line(),
+            caller.0.column(),
+            summary.count,
+            summary.name,
+            last.file(),
+            last.0.line(),
+            last.0.column()
+        );
     }
 }
 
-impl<T: Default, E: core::fmt::Debug> ResultExt for Result<T, E> {
-    type Output = T;
+/// Displays the summary of a [`FailureScope`], excluding its location.
+#[cfg(target_has_atomic = "64")]
+struct Summary<'a> {
+    name: &'a str,
+    count: u64,
+    last: &'a Location<'a>,
+}
 
-    #[track_caller]
-    fn unwrap_or_default_log(self) -> T {
+#[cfg(target_has_atomic = "64")]
+impl core::fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self { name, count, last } = self;
+        write!(
+            f,
+            "{count} failed unwraps in {name}, most recently at {}",
+            Loc(last)
+        )
+    }
+}
+
+/// Logs a present value at `level`, bypassing the hook and failure count.
+#[track_caller]
+fn resolved(level: Level, resolved: Resolved) {
+    let caller = Location::caller();
+    if mute::muted() {
+        // Reports are muted by a `MuteGuard`.
+    } else if let Some(sink) = sink::sink() {
+        sink.report(level, caller, format_args!("{resolved}"));
+    } else {
+        emit_resolved(level, caller, resolved);
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing", feature = "defmt")),
+    allow(unused_variables)
+)]
+fn emit_resolved(level: Level, caller: &Location<'_>, resolved: Resolved) {
+    let caller = Loc(caller);
+    #[cfg(all(feature = "log", not(feature = "kv")))]
+    {
+        LogSink.log_to(
+            Target::default(),
+            level,
+            caller.0,
+            format_args!("{resolved}"),
+        );
+    }
+    #[cfg(feature = "kv")]
+    if level.within_log_max() {
+        use log::kv::Value;
+        log_record(
+            Target::default(),
+            level,
+            caller,
+            &[
+                Some(("location", Value::from_dyn_display(&caller))),
+                Some(("file", Value::from(caller.file()))),
+                Some(("line", Value::from(caller.0.line()))),
+                Some(("column", Value::from(caller.0.column()))),
+            ],
+            format_args!("{caller} {resolved}"),
+        );
+    }
+    #[cfg(feature = "tracing")]
+    match level {
+        Level::Error => tracing::error!(location = %caller, defaulted = false, "{resolved}"),
+        Level::Warn => tracing::warn!(location = %caller, defaulted = false, "{resolved}"),
+        Level::Info => tracing::info!(location = %caller, defaulted = false, "{resolved}"),
+        Level::Debug => tracing::debug!(location = %caller, defaulted = false, "{resolved}"),
+        Level::Trace => tracing::trace!(location = %caller, defaulted = false, "{resolved}"),
+    }
+    #[cfg(feature = "defmt")]
+    {
+        let (file, line, column) = (caller.file(), caller.0.line(), caller.0.column());
+        macro_rules! event {
+            ($($arg:tt)*) => {
+                match level {
+                    Level::Error => defmt::error!($($arg)*),
+                    Level::Warn => defmt::warn!($($arg)*),
+                    Level::Info => defmt::info!($($arg)*),
+                    Level::Debug => defmt::debug!($($arg)*),
+                    Level::Trace => defmt::trace!($($arg)*),
+                }
+            };
+        }
+        match resolved {
+            Resolved::Some(x) => event!(
+                "{=str}:{=u32}:{=u32} resolved `Some({})`",
+                file,
+                line,
+                column,
+                DynFormat(x)
+            ),
+            Resolved::Ok(x) => event!(
+                "{=str}:{=u32}:{=u32} resolved `Ok({})`",
+                file,
+                line,
+                column,
+                DynFormat(x)
+            ),
+        }
+    }
+}
+
+/// Counts a failure, and records its error for [`last_error`], whether or not it is logged.
+fn record_failure(caller: &'static Location<'static>, encountered: Encountered<'_>) {
+    count_failure(caller, encountered.kind());
+    #[cfg(feature = "alloc")]
+    match encountered {
+        #[cfg(not(feature = "defmt"))]
+        Encountered::Err(err) | Encountered::ErrOf(_, err) => {
+            set_last_error(format_args!("{err:?}"))
+        }
+        #[cfg(feature = "std")]
+        Encountered::ErrChain(err) => set_last_error(format_args!("{err:?}")),
+        _ => {}
+    }
+}
+
+#[cfg_attr(
+    any(not(target_has_atomic = "64"), not(feature = "metrics")),
+    allow(unused_variables)
+)]
+fn count_failure(caller: &'static Location<'static>, kind: FailureKind) {
+    #[cfg(feature = "metrics")]
+    count_metric(kind);
+    #[cfg(target_has_atomic = "64")]
+    {
+        LAST_FAILURE.set(Some(caller));
+        let count = FAILURE_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+        let threshold = PANIC_THRESHOLD.load(Ordering::Relaxed);
+        if count > threshold {
+            threshold_exceeded(count, threshold, caller);
+        }
+    }
+}
+
+/// Increments the `unwrap_log.failures` counter for `kind`.
+///
+/// Each label is a literal, so that `metrics` keeps its key in a `static` rather than allocating.
+#[cfg(feature = "metrics")]
+fn count_metric(kind: FailureKind) {
+    macro_rules! counter {
+        ($kind:literal) => {
+            metrics::counter!("unwrap_log.failures", "kind" => $kind)
+        };
+    }
+    let counter = match kind {
+        FailureKind::None => counter!("none"),
+        FailureKind::Err => counter!("err"),
+        FailureKind::ErrNoDbg => counter!("err_no_dbg"),
+        FailureKind::Ok => counter!("ok"),
+        FailureKind::FilteredOut => counter!("filtered_out"),
+        FailureKind::Some => counter!("some"),
+    };
+    counter.increment(1);
+}
+
+#[cold]
+#[inline(never)]
+#[cfg(target_has_atomic = "64")]
+fn threshold_exceeded(count: u64, threshold: u64, caller: &Location<'_>) -> ! {
+    panic!(
+        "{count} failed unwraps exceeded the panic threshold of {threshold}, most recently at {}",
+        Loc(caller)
+    );
+}
+
+/// A present value, as logged by the `_verbose` and `inspect_` methods.
+#[derive(Clone, Copy)]
+enum Resolved<'a> {
+    Some(&'a DynPayload<'a>),
+    Ok(&'a DynPayload<'a>),
+}
+
+impl core::fmt::Display for Resolved<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Ok(x) => x,
-            Err(err) => {
-                result_error(&err);
-                T::default()
-            }
+            #[cfg(not(feature = "defmt"))]
+            Self::Some(x) => write!(f, "resolved `Some({x:?})`"),
+            #[cfg(feature = "defmt")]
+            Self::Some(_) => f.write_str("resolved `Some(..)`"),
+            #[cfg(not(feature = "defmt"))]
+            Self::Ok(x) => write!(f, "resolved `Ok({x:?})`"),
+            #[cfg(feature = "defmt")]
+            Self::Ok(_) => f.write_str("resolved `Ok(..)`"),
         }
     }
+}
 
-    #[track_caller]
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> T) -> T {
+/// Displays the inner value in its alternate form, as with `{:#}`.
+#[cfg(feature = "anyhow")]
+struct Alternate<'a>(&'a dyn core::fmt::Display);
+
+#[cfg(feature = "anyhow")]
+impl core::fmt::Display for Alternate<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+/// What was encountered in place of a present value.
+#[derive(Clone, Copy)]
+enum Encountered<'a> {
+    None,
+    NoneOf(&'static str),
+    Err(&'a DynPayload<'a>),
+    ErrOf((&'static str, &'static str), &'a DynPayload<'a>),
+    Ok(&'a DynPayload<'a>),
+    ErrDisplay(&'a dyn core::fmt::Display),
+    #[cfg(feature = "std")]
+    ErrChain(&'a dyn std::error::Error),
+    ErrNoDbg,
+    ErrRedacted(&'a str),
+    ErrCode(u32),
+    FilteredOut(&'a DynPayload<'a>),
+    TookNone,
+    NoneIn(&'static str),
+    SomeIn(&'static str),
+    OverwroteSome,
+}
+
+impl Encountered<'_> {
+    fn kind(self) -> FailureKind {
         match self {
-            Ok(x) => x,
-            Err(err) => {
-                result_error(&err);
-                f()
-            }
+            Self::None | Self::NoneOf(_) | Self::TookNone | Self::NoneIn(_) => FailureKind::None,
+            Self::Err(_)
+            | Self::ErrOf(..)
+            | Self::ErrDisplay(_)
+            | Self::ErrRedacted(_)
+            | Self::ErrCode(_) => FailureKind::Err,
+            #[cfg(feature = "std")]
+            Self::ErrChain(_) => FailureKind::Err,
+            Self::ErrNoDbg => FailureKind::ErrNoDbg,
+            Self::Ok(_) => FailureKind::Ok,
+            Self::FilteredOut(_) => FailureKind::FilteredOut,
+            Self::SomeIn(_) | Self::OverwroteSome => FailureKind::Some,
         }
     }
+}
 
-    #[track_caller]
-    fn unwrap_or_log(self, default: T) -> T {
+impl core::fmt::Display for Encountered<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let messages = MESSAGES.get();
         match self {
-            Ok(x) => x,
-            Err(err) => {
-                result_error(&err);
-                default
+            Self::None => return f.write_str(messages.none),
+            #[cfg(not(feature = "defmt"))]
+            Self::Err(err) => {
+                return write_template(f, messages.err, format_args!("{:?}", Dbg(err)))
+            }
+            #[cfg(feature = "defmt")]
+            Self::Err(_) => return write_template(f, messages.err, format_args!("..")),
+            Self::ErrNoDbg => return f.write_str(messages.err_no_dbg),
+            Self::FilteredOut(_) => f.write_str("filtered out ")?,
+            Self::TookNone => f.write_str("took from ")?,
+            Self::OverwroteSome => f.write_str("overwrote ")?,
+            _ => f.write_str("encountered ")?,
+        }
+        match self {
+            // Written in full from `messages` above.
+            Self::None | Self::Err(_) | Self::ErrNoDbg => Ok(()),
+            Self::TookNone => f.write_str("`None`"),
+            Self::NoneIn(place) => write!(f, "`None` in {place}"),
+            Self::SomeIn(place) => write!(f, "`Some` in {place}"),
+            Self::OverwroteSome => f.write_str("`Some`"),
+            Self::NoneOf(ty) => write!(f, "`None::<{ty}>`"),
+            #[cfg(not(feature = "defmt"))]
+            Self::ErrOf((t, e), err) => write!(f, "`Err::<{t}, {e}>({:?})`", Dbg(err)),
+            #[cfg(feature = "defmt")]
+            Self::ErrOf((t, e), _) => write!(f, "`Err::<{t}, {e}>(..)`"),
+            Self::ErrDisplay(err) => write!(f, "`Err`: {err}"),
+            #[cfg(feature = "std")]
+            Self::ErrChain(err) => {
+                write!(f, "`Err({:?})`", Dbg(err))?;
+                let mut source = err.source();
+                while let Some(err) = source {
+                    write!(f, " caused by: {err}")?;
+                    source = err.source();
+                }
+                Ok(())
             }
+            Self::ErrRedacted(token) => write!(f, "`Err(_)`: {token}"),
+            Self::ErrCode(code) => write!(f, "`Err[kind={code}]`"),
+            #[cfg(not(feature = "defmt"))]
+            Self::Ok(x) => write!(f, "`Ok({x:?})`"),
+            #[cfg(feature = "defmt")]
+            Self::Ok(_) => f.write_str("`Ok(..)`"),
+            #[cfg(not(feature = "defmt"))]
+            Self::FilteredOut(x) => write!(f, "`Some({x:?})`"),
+            #[cfg(feature = "defmt")]
+            Self::FilteredOut(_) => f.write_str("`Some(..)`"),
         }
     }
 }
 
-impl<T: Default, E> ResultExtNoDbg for Result<T, E> {
-    type Output = T;
+/// Formats the type parameters of an `Err`, if known, as in `::<T, E>`.
+#[cfg(feature = "kv")]
+struct ErrTypes(Option<(&'static str, &'static str)>);
 
-    #[track_caller]
-    fn unwrap_or_default_log(self) -> T {
-        if let Ok(x) = self {
-            x
-        } else {
-            no_dbg_error();
-            T::default()
+#[cfg(feature = "kv")]
+impl core::fmt::Display for ErrTypes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some((t, e)) => write!(f, "::<{t}, {e}>"),
+            None => Ok(()),
         }
     }
+}
 
-    #[track_caller]
-    fn unwrap_or_else_log(self, f: impl FnOnce() -> T) -> T {
-        if let Ok(x) = self {
-            x
+/// Formats an error with `{:?}`, or `{:#?}` if [`set_pretty_debug`] is enabled.
+#[cfg(any(not(feature = "defmt"), feature = "std"))]
+struct Dbg<'a, T: ?Sized>(&'a T);
+
+#[cfg(any(not(feature = "defmt"), feature = "std"))]
+impl<T: core::fmt::Debug + ?Sized> core::fmt::Debug for Dbg<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if PRETTY_DEBUG.load(Ordering::Relaxed) {
+            write!(f, "{:#?}", self.0)
         } else {
-            no_dbg_error();
-            f()
+            write!(f, "{:?}", self.0)
         }
     }
+}
+
+/// Writes `template` with its first `{}` replaced by `payload`.
+fn write_template(
+    f: &mut core::fmt::Formatter<'_>,
+    template: &str,
+    payload: core::fmt::Arguments<'_>,
+) -> core::fmt::Result {
+    match template.split_once("{}") {
+        Some((before, after)) => write!(f, "{before}{payload}{after}"),
+        None => f.write_str(template),
+    }
+}
 
-    #[track_caller]
-    fn unwrap_or_log(self, default: T) -> T {
-        if let Ok(x) = self {
-            x
-        } else {
-            no_dbg_error();
-            default
+/// The human-readable description of a failure, excluding its location.
+struct Message<'a> {
+    msg: Option<&'a str>,
+    encountered: Encountered<'a>,
+    occurrence: Option<u64>,
+    sequence: Option<u64>,
+    #[cfg(feature = "kv")]
+    context: Option<(&'static str, &'a str)>,
+    #[cfg(feature = "log")]
+    target: Option<&'static str>,
+    #[cfg(feature = "log")]
+    module: Option<&'static str>,
+}
+
+impl core::fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = Msg(self.msg);
+        let occurrence = Occurrence(self.occurrence);
+        let sequence = Sequence(self.sequence);
+        write!(f, "{sequence}{Prefix}{msg}{}{occurrence}", self.encountered)
+    }
+}
+
+/// Displays a location as `file:line:column`, with the prefix set by [`strip_path_prefix`]
+/// removed from the file.
+#[derive(Clone, Copy)]
+pub(crate) struct Loc<'a>(pub(crate) &'a Location<'a>);
+
+impl<'a> Loc<'a> {
+    fn file(self) -> &'a str {
+        let file = self.0.file();
+        PATH_PREFIX
+            .get()
+            .and_then(|prefix| file.strip_prefix(prefix))
+            .unwrap_or(file)
+    }
+}
+
+impl core::fmt::Display for Loc<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}:{}", self.file(), self.0.line(), self.0.column())
+    }
+}
+
+/// Displays the prefix set by [`set_message_prefix`], if there is one.
+struct Prefix;
+
+impl core::fmt::Display for Prefix {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(message_prefix().unwrap_or(""))
+    }
+}
+
+/// Displays a user-provided message as a prefix, if there is one.
+struct Msg<'a>(Option<&'a str>);
+
+impl core::fmt::Display for Msg<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(msg) => write!(f, "{msg}: "),
+            None => Ok(()),
         }
     }
 }
 
-#[cold]
-#[inline(never)]
-#[track_caller]
-fn option_error() {
-    let caller = core::panic::Location::caller();
-    log::warn!("{caller} encountered `None`");
+/// Displays how many times a call site has failed, if known.
+struct Occurrence(Option<u64>);
+
+impl core::fmt::Display for Occurrence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(n) => write!(f, " (x{n})"),
+            None => Ok(()),
+        }
+    }
 }
 
-#[cold]
-#[inline(never)]
-#[track_caller]
-fn result_error(err: &dyn core::fmt::Debug) {
-    let caller = core::panic::Location::caller();
-    log::warn!("{caller} encountered `Err({err:?})`");
+/// Displays the global sequence number of a failure, if numbered, as in `#42 `.
+struct Sequence(Option<u64>);
+
+impl core::fmt::Display for Sequence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(n) => write!(f, "#{n} "),
+            None => Ok(()),
+        }
+    }
 }
 
-#[cold]
-#[inline(never)]
-#[track_caller]
-fn no_dbg_error() {
-    let caller = core::panic::Location::caller();
-    log::warn!("{caller} encountered `Err(_)`");
+/// Object-safe stand-in for `defmt::Format`, which can't be made into a trait object.
+#[cfg(feature = "defmt")]
+trait ErasedFormat {
+    fn erased_format(&self, fmt: defmt::Formatter<'_>);
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format> ErasedFormat for T {
+    fn erased_format(&self, fmt: defmt::Formatter<'_>) {
+        self.format(fmt);
+    }
+}
+
+#[cfg(feature = "defmt")]
+struct DynFormat<'a>(&'a dyn ErasedFormat);
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DynFormat<'_> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        self.0.erased_format(fmt);
+    }
+}
+
+/// A line of at most [`FALLBACK_LEN`] bytes, which silently truncates at a `char` boundary.
+#[cfg(feature = "fallback")]
+struct Line {
+    buf: [u8; FALLBACK_LEN],
+    len: usize,
+}
+
+#[cfg(feature = "fallback")]
+impl Line {
+    const fn new() -> Self {
+        Self {
+            buf: [0; FALLBACK_LEN],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole `char`s are ever written, so this never fails.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "fallback")]
+impl core::fmt::Write for Line {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let mut end = s.len().min(FALLBACK_LEN - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        Ok(())
+    }
+}
+
+/// A value behind a spin lock, for globals which don't fit in a single atomic.
+///
+/// This is why the crate isn't interrupt-safe: a handler which preempts a holder of the lock on
+/// the same core waits for it forever.
+struct Global<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: all access to `value` happens while holding `locked`.
+unsafe impl<T: Send> Sync for Global<T> {}
+
+impl<T> Global<T> {
+    const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let _unlock = Unlock(&self.locked);
+        // SAFETY: we hold the lock, so no other reference to `value` exists.
+        f(unsafe { &mut *self.value.get() })
+    }
+}
+
+/// Releases the lock of a [`Global`] when dropped, even if its closure panics.
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T: Copy> Global<T> {
+    fn get(&self) -> T {
+        self.with(|value| *value)
+    }
+
+    fn set(&self, new: T) {
+        self.with(|value| *value = new);
+    }
 }
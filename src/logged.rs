@@ -0,0 +1,106 @@
+use core::ops::{Deref, DerefMut};
+
+/// A value which records whether it was substituted after a failed unwrap.
+///
+/// Returned by [`unwrap_or_default_logged`](crate::OptionExt::unwrap_or_default_logged), so that
+/// fallbacks stay visible in the types of the code which handles them.
+///
+/// ```rust
+/// use unwrap_log::OptionExt;
+///
+/// let x = None::<i32>.unwrap_or_default_logged();
+/// assert!(x.was_defaulted());
+/// assert_eq!(*x, 0);
+///
+/// let y = Some(5).unwrap_or_default_logged();
+/// assert!(!y.was_defaulted());
+/// assert_eq!(y.into_inner(), 5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LoggedDefault<T> {
+    value: T,
+    defaulted: bool,
+}
+
+impl<T> LoggedDefault<T> {
+    /// Wraps a value which was present.
+    pub const fn real(value: T) -> Self {
+        Self {
+            value,
+            defaulted: false,
+        }
+    }
+
+    /// Wraps a value which was substituted in place of a missing one.
+    pub const fn defaulted(value: T) -> Self {
+        Self {
+            value,
+            defaulted: true,
+        }
+    }
+
+    /// Returns whether the value was substituted in place of a missing one.
+    pub const fn was_defaulted(&self) -> bool {
+        self.defaulted
+    }
+
+    /// Returns the wrapped value, discarding whether it was substituted.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for LoggedDefault<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for LoggedDefault<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// A value which must be passed through a metric hook, in case it was substituted after a failed
+/// unwrap.
+///
+/// Returned by [`unwrap_or_default_log_metered`](crate::OptionExt::unwrap_or_default_log_metered),
+/// and marked `#[must_use]` so that forgetting to record the fallback is a warning.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use unwrap_log::OptionExt;
+///
+/// static DEFAULTED: AtomicU32 = AtomicU32::new(0);
+///
+/// let x: i32 = None.unwrap_or_default_log_metered().or_metric(|| {
+///     DEFAULTED.fetch_add(1, Ordering::Relaxed);
+/// });
+/// assert_eq!(x, 0);
+/// assert_eq!(DEFAULTED.load(Ordering::Relaxed), 1);
+/// ```
+#[must_use = "call `or_metric` to record when a default was substituted"]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Logged<T>(LoggedDefault<T>);
+
+impl<T> Logged<T> {
+    pub(crate) const fn new(value: LoggedDefault<T>) -> Self {
+        Self(value)
+    }
+
+    /// Calls `metric` if the value was substituted, and returns the value.
+    pub fn or_metric(self, metric: impl FnOnce()) -> T {
+        if self.0.was_defaulted() {
+            metric();
+        }
+        self.0.into_inner()
+    }
+
+    /// Returns the value as a [`LoggedDefault`], without calling a metric hook.
+    pub fn into_logged_default(self) -> LoggedDefault<T> {
+        self.0
+    }
+}
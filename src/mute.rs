@@ -0,0 +1,78 @@
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MUTED: core::cell::Cell<u32> = const { core::cell::Cell::new(0) };
+}
+
+#[cfg(not(feature = "std"))]
+static MUTED: AtomicU32 = AtomicU32::new(0);
+
+/// Mutes failure reports until the returned guard is dropped.
+///
+/// While muted, failures are still tallied by [`failure_count`](crate::failure_count), and by
+/// the counter of the `metrics` feature, and their errors are still recorded for `last_error`
+/// with the `alloc` feature, but nothing else sees them: neither the backends, nor any
+/// [sink](crate::set_sink) or [hook](crate::set_hook). This suits expected failures, such
+/// as in a benchmark or a noisy initialization, without changing each call site.
+///
+/// With `std`, muting applies only to the current thread, and the guard can't be sent to another.
+/// Without it, muting is process-global, and applies to every thread.
+///
+/// Guards nest: reports stay muted until every guard is dropped, whatever the order. A guard
+/// created while the thread's locals are being destroyed has no effect.
+///
+/// ```rust
+/// use unwrap_log::{OptionExt, RingSink};
+///
+/// static RING: RingSink<4> = RingSink::new();
+/// RING.install();
+///
+/// {
+///     let _muted = unwrap_log::mute();
+///     let x: i32 = None.unwrap_or_default_log(); // Not logged.
+///     {
+///         let _again = unwrap_log::mute();
+///     }
+///     let y: i32 = None.unwrap_or_default_log(); // Still not logged.
+/// }
+/// let z: i32 = None.unwrap_or_default_log(); // Logged.
+/// assert_eq!(RING.entries().count(), 1);
+/// ```
+pub fn mute() -> MuteGuard {
+    // During thread-local teardown, the flag is gone and nothing is muted.
+    #[cfg(feature = "std")]
+    let _ = MUTED.try_with(|muted| muted.set(muted.get() + 1));
+    #[cfg(not(feature = "std"))]
+    MUTED.fetch_add(1, Ordering::Relaxed);
+    MuteGuard {
+        #[cfg(feature = "std")]
+        _not_send: core::marker::PhantomData,
+    }
+}
+
+/// A guard returned by [`mute`], which restores failure reports when dropped.
+#[must_use = "reports are muted only until the guard is dropped"]
+#[derive(Debug)]
+pub struct MuteGuard {
+    #[cfg(feature = "std")]
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl Drop for MuteGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        let _ = MUTED.try_with(|muted| muted.set(muted.get().saturating_sub(1)));
+        #[cfg(not(feature = "std"))]
+        MUTED.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns whether a [`MuteGuard`] is alive, on this thread with `std`.
+pub(crate) fn muted() -> bool {
+    #[cfg(feature = "std")]
+    return MUTED.try_with(|muted| muted.get() > 0).unwrap_or(false);
+    #[cfg(not(feature = "std"))]
+    return MUTED.load(Ordering::Relaxed) > 0;
+}
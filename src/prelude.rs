@@ -0,0 +1,28 @@
+//! Re-exports every extension trait, for glob-importing.
+//!
+//! No two of these traits share a method name for the same receiver, so importing them all
+//! never makes a call ambiguous.
+//!
+//! ```rust
+//! use unwrap_log::prelude::*;
+//!
+//! struct Opaque;
+//!
+//! let x: i32 = Err("oops").unwrap_or_default_log();
+//! let y: i32 = Err(Opaque).unwrap_or_default_log_no_dbg();
+//! let z: i32 = Err("oops").unwrap_or_default_log_display();
+//! assert_eq!(x + y + z, 0);
+//! ```
+
+#[cfg(feature = "anyhow")]
+pub use crate::ResultExtAnyhow;
+#[cfg(feature = "std")]
+pub use crate::ResultExtSource;
+pub use crate::{
+    IterOptionExt, IterResultExt, LogDefault, OptionArrayExt, OptionDerefExt, OptionExt,
+    OptionFlattenExt, OptionMutExt, OptionNonZeroExt, OptionRefExt, OptionTransposeExt,
+    ResultErrExt, ResultExt, ResultExtCode, ResultExtDisplay, ResultExtNoDbg, ResultExtRedact,
+    ResultRefExt, TryIntoExt,
+};
+#[cfg(feature = "async")]
+pub use crate::{OptionExtAsync, ResultExtAsync};
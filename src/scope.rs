@@ -0,0 +1,57 @@
+use core::panic::Location;
+
+use crate::failure_count;
+
+/// A guard which logs a summary of the failures since it was created, when dropped.
+///
+/// This gives one rollup per operation, such as a request handler, in place of or alongside the
+/// per-call logs. The summary is logged at the [default level](crate::default_level) at the
+/// location where the scope was created, and only if there were failures.
+///
+/// Failures are counted with [`failure_count`], which is process-global, so failures on other
+/// threads during the scope are included in its tally.
+///
+/// ```rust
+/// use unwrap_log::{FailureScope, OptionExt};
+///
+/// fn handle_request() {
+///     let _scope = FailureScope::new("handle_request");
+///     let _: i32 = None.unwrap_or_default_log();
+///     let _: i32 = None.unwrap_or_default_log();
+///     // On return, logs "... 2 failed unwraps in handle_request, most recently at ...".
+/// }
+///
+/// handle_request();
+/// ```
+#[derive(Debug)]
+pub struct FailureScope {
+    name: &'static str,
+    start: u64,
+    location: &'static Location<'static>,
+}
+
+impl FailureScope {
+    /// Opens a scope named `name`, counting failures from now until it is dropped.
+    #[track_caller]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            start: failure_count(),
+            location: Location::caller(),
+        }
+    }
+
+    /// Returns the number of failures since this scope was opened.
+    pub fn failures(&self) -> u64 {
+        failure_count().saturating_sub(self.start)
+    }
+}
+
+impl Drop for FailureScope {
+    fn drop(&mut self) {
+        let count = self.failures();
+        if count > 0 {
+            crate::summarize(self.location, self.name, count);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use core::fmt::Arguments;
+use core::panic::Location;
+
+use crate::{Global, Level};
+
+/// The maximum number of sinks which can be installed at once with [`add_sink`].
+pub const MAX_SINKS: usize = 4;
+
+static SINKS: Global<[Option<&'static dyn Sink>; MAX_SINKS]> = Global::new([None; MAX_SINKS]);
+
+/// A destination for failure reports, for environments with their own logging channel.
+///
+/// Install one with [`set_sink`]. The `payload` is everything following the location, such as
+/// ``encountered `None` ``.
+///
+/// ```rust
+/// use core::fmt::Arguments;
+/// use core::panic::Location;
+/// use unwrap_log::{Level, OptionExt, Sink};
+///
+/// struct Console;
+///
+/// impl Sink for Console {
+///     fn report(&self, level: Level, location: &Location<'_>, payload: Arguments<'_>) {
+///         println!("[{level}] {location} {payload}");
+///     }
+/// }
+///
+/// unwrap_log::set_sink(&Console);
+/// let x: i32 = None.unwrap_or_default_log();
+/// ```
+pub trait Sink: Sync {
+    /// Reports a single failure at `location`.
+    ///
+    /// The location is always `'static`, so a sink may keep it, as [`RingSink`] does, but an
+    /// implementation may also accept any `&Location<'_>`.
+    fn report(&self, level: Level, location: &'static Location<'static>, payload: Arguments<'_>);
+}
+
+/// A [`Sink`] which emits through the `log` crate, using the [target](crate::set_target) if set.
+///
+/// This behaves like the `log` feature without `kv`.
+#[cfg(feature = "log")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogSink;
+
+#[cfg(feature = "log")]
+impl Sink for LogSink {
+    fn report(&self, level: Level, location: &Location<'_>, payload: Arguments<'_>) {
+        self.log_to(Target::default(), level, location, payload);
+    }
+}
+
+/// Where a `log` record is attributed.
+#[cfg(feature = "log")]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Target<'a> {
+    /// A target which takes precedence over the [global one](crate::set_target).
+    pub(crate) explicit: Option<&'static str>,
+    /// The module of the call site, if known, which is the target if no other is set.
+    pub(crate) module: Option<&'a str>,
+}
+
+#[cfg(feature = "log")]
+impl<'a> Target<'a> {
+    pub(crate) fn name(self) -> &'a str {
+        self.explicit
+            .or(crate::target())
+            .or(self.module)
+            .unwrap_or("unwrap_log")
+    }
+}
+
+#[cfg(feature = "log")]
+impl LogSink {
+    /// Returns whether the logger would keep a record at `level`, with the same target as
+    /// [`log_to`](Self::log_to).
+    pub(crate) fn enabled(&self, target: Target<'_>, level: Level) -> bool {
+        let target = target.name();
+        level.within_log_max()
+            && log::logger().enabled(
+                &log::Metadata::builder()
+                    .level(level.into())
+                    .target(target)
+                    .build(),
+            )
+    }
+
+    /// Like `report`, but attributed to `target`, rather than to the default one.
+    pub(crate) fn log_to(
+        &self,
+        target: Target<'_>,
+        level: Level,
+        location: &Location<'_>,
+        payload: Arguments<'_>,
+    ) {
+        if !level.within_log_max() {
+            return;
+        }
+        let location = crate::Loc(location);
+        log::logger().log(
+            &log::Record::builder()
+                .args(format_args!("{location} {payload}"))
+                .level(level.into())
+                .target(target.name())
+                .module_path(target.module)
+                .file(Some(location.file()))
+                .line(Some(location.0.line()))
+                .build(),
+        );
+    }
+}
+
+/// Installs a sink which receives every failure, in place of the backends enabled by features.
+///
+/// This is process-global, and affects all call sites, including those in other crates.
+/// It replaces every sink installed so far, including those added with [`add_sink`].
+/// A hook installed with [`set_hook`](crate::set_hook) takes precedence over the sink.
+pub fn set_sink(sink: &'static dyn Sink) {
+    let mut sinks = [None; MAX_SINKS];
+    sinks[0] = Some(sink);
+    SINKS.set(sinks);
+}
+
+/// Installs a sink alongside those already installed, so that each receives every failure in
+/// the order they were added.
+///
+/// To keep both human-readable logs and a custom sink, add [`LogSink`] too. At most
+/// [`MAX_SINKS`] can be installed; once that many are, this returns `false` and leaves the
+/// installed sinks unchanged.
+///
+/// ```rust
+/// use core::fmt::Arguments;
+/// use core::panic::Location;
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use unwrap_log::{Level, OptionExt, Sink};
+///
+/// struct Metrics(AtomicU32);
+///
+/// impl Sink for Metrics {
+///     fn report(&self, _: Level, _: &Location<'_>, _: Arguments<'_>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// static METRICS: Metrics = Metrics(AtomicU32::new(0));
+///
+/// #[cfg(feature = "log")]
+/// assert!(unwrap_log::add_sink(&unwrap_log::LogSink));
+/// assert!(unwrap_log::add_sink(&METRICS));
+/// let x: i32 = None.unwrap_or_default_log();
+/// assert_eq!(METRICS.0.load(Ordering::Relaxed), 1);
+/// ```
+pub fn add_sink(sink: &'static dyn Sink) -> bool {
+    SINKS.with(|sinks| {
+        if let Some(slot) = sinks.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(sink);
+            true
+        } else {
+            false
+        }
+    })
+}
+
+/// Removes every sink installed with [`set_sink`] or [`add_sink`], restoring the backends
+/// enabled by features.
+pub fn clear_sink() {
+    SINKS.set([None; MAX_SINKS]);
+}
+
+/// The installed sinks, which report to each of them in turn.
+#[derive(Clone, Copy)]
+pub(crate) struct Sinks([Option<&'static dyn Sink>; MAX_SINKS]);
+
+impl Sink for Sinks {
+    fn report(&self, level: Level, location: &'static Location<'static>, payload: Arguments<'_>) {
+        for sink in self.0.iter().flatten() {
+            sink.report(level, location, payload);
+        }
+    }
+}
+
+pub(crate) fn sink() -> Option<Sinks> {
+    let sinks = SINKS.get();
+    sinks[0].is_some().then_some(Sinks(sinks))
+}
+
+/// A [`Sink`] which keeps the locations of the last `N` failures, overwriting the oldest once
+/// full, as a flight recorder to read back after a crash.
+///
+/// This needs no allocator, and can be stored in a `static`.
+///
+/// ```rust
+/// use unwrap_log::{OptionExt, RingSink};
+///
+/// static RING: RingSink<2> = RingSink::new();
+///
+/// RING.install();
+/// let lines = [line!() + 1, line!() + 2, line!() + 3];
+/// let x: i32 = None.unwrap_or_default_log();
+/// let y: i32 = None.unwrap_or_default_log();
+/// let z: i32 = None.unwrap_or_default_log();
+///
+/// let entries: Vec<u32> = RING.entries().map(|location| location.line()).collect();
+/// assert_eq!(entries, lines[1..]);
+/// ```
+pub struct RingSink<const N: usize> {
+    ring: Global<Ring<N>>,
+}
+
+#[derive(Clone, Copy)]
+struct Ring<const N: usize> {
+    entries: [Option<&'static Location<'static>>; N],
+    /// The slot to be written next, which holds the oldest entry once full.
+    next: usize,
+}
+
+impl<const N: usize> RingSink<N> {
+    /// Creates an empty ring, which can be stored in a `static`.
+    pub const fn new() -> Self {
+        Self {
+            ring: Global::new(Ring {
+                entries: [None; N],
+                next: 0,
+            }),
+        }
+    }
+
+    /// Installs this as the only [sink](set_sink).
+    pub fn install(&'static self) {
+        set_sink(self);
+    }
+
+    /// Returns the locations kept so far, from oldest to newest.
+    pub fn entries(&self) -> impl Iterator<Item = &'static Location<'static>> {
+        let ring = self.ring.get();
+        let mut entries = [None; N];
+        for (i, entry) in entries.iter_mut().enumerate() {
+            *entry = ring.entries[(ring.next + i) % N];
+        }
+        entries.into_iter().flatten()
+    }
+
+    /// Forgets every location kept so far.
+    pub fn clear(&self) {
+        self.ring.set(Ring {
+            entries: [None; N],
+            next: 0,
+        });
+    }
+}
+
+impl<const N: usize> Default for RingSink<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Sink for RingSink<N> {
+    fn report(&self, _: Level, location: &'static Location<'static>, _: Arguments<'_>) {
+        if N == 0 {
+            return;
+        }
+        self.ring.with(|ring| {
+            ring.entries[ring.next] = Some(location);
+            ring.next = (ring.next + 1) % N;
+        });
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// The call site of one of this crate's macros, which, unlike a `#[track_caller]` method, knows
+/// its module.
+#[derive(Clone, Copy)]
+pub struct Site<'a> {
+    #[cfg_attr(not(any(feature = "log", feature = "alloc")), allow(dead_code))]
+    pub(crate) module: Option<&'static str>,
+    pub(crate) msg: Option<&'a str>,
+    pub(crate) target: Option<&'static str>,
+    pub(crate) gate: Option<Gate<'a>>,
+}
+
+impl Site<'_> {
+    /// The site of a method call, whose module is unknown.
+    pub(crate) const UNKNOWN: Self = Self {
+        module: None,
+        msg: None,
+        target: None,
+        gate: None,
+    };
+}
+
+impl<'a> Site<'a> {
+    pub const fn new(module: &'static str) -> Self {
+        Self {
+            module: Some(module),
+            ..Self::UNKNOWN
+        }
+    }
+
+    pub const fn msg(self, msg: &'a str) -> Self {
+        Self {
+            msg: Some(msg),
+            ..self
+        }
+    }
+
+    pub const fn target(self, target: &'static str) -> Self {
+        Self {
+            target: Some(target),
+            ..self
+        }
+    }
+
+    pub const fn gate(self, gate: Gate<'a>) -> Self {
+        Self {
+            gate: Some(gate),
+            ..self
+        }
+    }
+}
+
+/// Decides whether a failure passes a rate limit, for the `_once`, `_sampled`, and `_throttled`
+/// methods, or tallies it, for the `_counted` ones.
+#[derive(Clone, Copy)]
+pub enum Gate<'a> {
+    Once(&'a AtomicBool),
+    Counted(&'a AtomicU32),
+    Sampled(&'a AtomicU32, u32),
+    #[cfg(target_has_atomic = "64")]
+    Throttled(&'a AtomicU64, u64),
+}
+
+impl Gate<'_> {
+    /// Returns whether the failure passes the rate limit, which a tally always does.
+    pub(crate) fn allows(self) -> bool {
+        match self {
+            Self::Once(logged) => !logged.swap(true, Ordering::Relaxed),
+            Self::Counted(_) => true,
+            Self::Sampled(count, n) => crate::sampled(count, n),
+            #[cfg(target_has_atomic = "64")]
+            Self::Throttled(next, interval_ms) => crate::throttled(next, interval_ms),
+        }
+    }
+}
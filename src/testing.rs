@@ -0,0 +1,91 @@
+//! A capturing [`Sink`], for asserting on failures in tests.
+//!
+//! This is only available with the `testing` feature, and is intended for tests only: every
+//! record is kept until it is taken, so a long-running program would grow without bound.
+//!
+//! ```rust
+//! use unwrap_log::testing::Capture;
+//! use unwrap_log::OptionExt;
+//!
+//! static CAPTURE: Capture = Capture::new();
+//!
+//! CAPTURE.install();
+//! let x: i32 = None.unwrap_or_default_log();
+//! let records = CAPTURE.take();
+//! assert_eq!(records.len(), 1);
+//! assert_eq!(records[0].level, unwrap_log::default_level());
+//! assert_eq!(records[0].line, line!() - 4);
+//! assert!(records[0].message.ends_with("encountered `None`"));
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Arguments;
+use core::panic::Location;
+
+use crate::{Global, Level, Sink};
+
+/// A single failure received by a [`Capture`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The level the failure was reported at.
+    pub level: Level,
+    /// The file of the failing call site.
+    pub file: String,
+    /// The line of the failing call site.
+    pub line: u32,
+    /// The column of the failing call site.
+    pub column: u32,
+    /// Everything following the location, such as ``encountered `None` ``.
+    pub message: String,
+}
+
+/// A [`Sink`] which records every failure it receives.
+///
+/// Like any sink, it only receives failures while no [hook](crate::set_hook) is installed.
+pub struct Capture {
+    records: Global<Vec<Record>>,
+}
+
+impl Capture {
+    /// Creates an empty capture, which can be stored in a `static`.
+    pub const fn new() -> Self {
+        Self {
+            records: Global::new(Vec::new()),
+        }
+    }
+
+    /// Installs this as the only [sink](crate::set_sink).
+    pub fn install(&'static self) {
+        crate::set_sink(self);
+    }
+
+    /// Returns a copy of the records received so far.
+    pub fn records(&self) -> Vec<Record> {
+        self.records.with(|records| records.clone())
+    }
+
+    /// Returns the records received so far, leaving none behind.
+    pub fn take(&self) -> Vec<Record> {
+        self.records.with(core::mem::take)
+    }
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sink for Capture {
+    fn report(&self, level: Level, location: &Location<'_>, payload: Arguments<'_>) {
+        let record = Record {
+            level,
+            file: location.file().to_string(),
+            line: location.line(),
+            column: location.column(),
+            message: payload.to_string(),
+        };
+        self.records.with(|records| records.push(record));
+    }
+}